@@ -0,0 +1,219 @@
+use std::fs;
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+use shared::{InputSource, Puzzle, Stage};
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Record {
+    pub day: u8,
+    pub stage: String,
+    pub min_ns: u64,
+    pub median_ns: u64,
+    pub max_ns: u64,
+}
+
+impl Record {
+    fn from_samples(day: u8, stage: Stage, samples: &[Duration]) -> Record {
+        let mut nanos = samples
+            .iter()
+            .map(|sample| sample.as_nanos() as u64)
+            .collect::<Vec<u64>>();
+        nanos.sort_unstable();
+
+        let median = match nanos.len() {
+            0 => 0,
+            len if len % 2 == 0 => (nanos[len / 2 - 1] + nanos[len / 2]) / 2,
+            len => nanos[len / 2],
+        };
+
+        Record {
+            day,
+            stage: stage.to_string(),
+            min_ns: nanos.first().copied().unwrap_or(0),
+            median_ns: median,
+            max_ns: nanos.last().copied().unwrap_or(0),
+        }
+    }
+}
+
+/// Machine-readable benchmark results. A saved results file doubles as a baseline.
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct Report {
+    pub iterations: usize,
+    pub records: Vec<Record>,
+}
+
+impl Report {
+    pub fn load(filename: &str) -> Result<Report, String> {
+        let contents = fs::read_to_string(filename)
+            .map_err(|err| format!("could not read {}: {}", filename, err))?;
+
+        serde_json::from_str(&contents).map_err(|err| format!("{}: {}", filename, err))
+    }
+
+    pub fn save(&self, filename: &str) -> Result<(), String> {
+        let contents = serde_json::to_string_pretty(self).map_err(|err| err.to_string())?;
+
+        fs::write(filename, contents + "\n")
+            .map_err(|err| format!("could not write {}: {}", filename, err))
+    }
+
+    fn baseline_for(&self, record: &Record) -> Option<&Record> {
+        self.records
+            .iter()
+            .find(|r| r.day == record.day && r.stage == record.stage)
+    }
+}
+
+/// Benchmarks every stage of each puzzle. Days whose input cannot be loaded are skipped with
+/// a message instead of aborting the whole run.
+pub fn bench(
+    puzzles: &[&dyn Puzzle],
+    iterations: usize,
+    input_for: &dyn Fn(&dyn Puzzle) -> InputSource,
+) -> Report {
+    let mut records = vec![];
+
+    for puzzle in puzzles {
+        let day = puzzle.day();
+        let samples = input_for(*puzzle)
+            .read()
+            .and_then(|lines| puzzle.bench(&lines, iterations));
+
+        match samples {
+            Ok(samples) => {
+                for (stage, stage_samples) in samples {
+                    records.push(Record::from_samples(day, stage, &stage_samples));
+                }
+            }
+            Err(err) => eprintln!("Day {}: skipped, {}", day, err),
+        }
+    }
+
+    Report {
+        iterations,
+        records,
+    }
+}
+
+/// Relative change of the median against the baseline, in percent.
+fn change_percent(record: &Record, baseline: &Record) -> f64 {
+    if baseline.median_ns == 0 {
+        return 0.0;
+    }
+
+    (record.median_ns as f64 - baseline.median_ns as f64) / baseline.median_ns as f64 * 100.0
+}
+
+fn format_ns(nanos: u64) -> String {
+    format!("{:.3}ms", nanos as f64 / 1_000_000.0)
+}
+
+/// Prints the results, comparing medians against the baseline when one is given. Returns the
+/// number of stages whose median got slower than `threshold` percent.
+pub fn report(report: &Report, baseline: Option<&Report>, threshold: f64) -> usize {
+    let mut regressions = 0;
+
+    for record in report.records.iter() {
+        let comparison = match baseline.and_then(|b| b.baseline_for(record)) {
+            Some(base) => {
+                let change = change_percent(record, base);
+                if change > threshold {
+                    regressions += 1;
+                    format!("{:+.1}% vs baseline  REGRESSION", change)
+                } else {
+                    format!("{:+.1}% vs baseline", change)
+                }
+            }
+            None if baseline.is_some() => "no baseline".to_string(),
+            None => "".to_string(),
+        };
+
+        let line = format!(
+            "Day {:>2} {:<7}  min {:>12}  median {:>12}  max {:>12}  {}",
+            record.day,
+            record.stage,
+            format_ns(record.min_ns),
+            format_ns(record.median_ns),
+            format_ns(record.max_ns),
+            comparison
+        );
+        println!("{}", line.trim_end());
+    }
+
+    if baseline.is_some() {
+        println!(
+            "\n{} regression(s) above {}% threshold",
+            regressions, threshold
+        );
+    }
+
+    regressions
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record(stage: &str, median_ns: u64) -> Record {
+        Record {
+            day: 1,
+            stage: stage.to_string(),
+            min_ns: median_ns,
+            median_ns,
+            max_ns: median_ns,
+        }
+    }
+
+    #[test]
+    fn it_summarises_samples() {
+        let samples = [5, 1, 3, 4, 2]
+            .iter()
+            .map(|ns| Duration::from_nanos(*ns))
+            .collect::<Vec<Duration>>();
+        let record = Record::from_samples(3, Stage::Parse, &samples);
+
+        assert_eq!(record.day, 3);
+        assert_eq!(record.stage, "parse");
+        assert_eq!((record.min_ns, record.median_ns, record.max_ns), (1, 3, 5));
+
+        let record = Record::from_samples(3, Stage::Parse, &samples[0..4]);
+        assert_eq!(record.median_ns, 3);
+    }
+
+    #[test]
+    fn it_flags_regressions_against_the_baseline() {
+        let baseline = Report {
+            iterations: 3,
+            records: vec![record("parse", 100), record("part 1", 100)],
+        };
+        let current = Report {
+            iterations: 3,
+            records: vec![
+                record("parse", 105),
+                record("part 1", 150),
+                record("part 2", 10),
+            ],
+        };
+
+        assert_eq!(
+            change_percent(&current.records[1], &baseline.records[1]),
+            50.0
+        );
+        assert_eq!(report(&current, Some(&baseline), 10.0), 1);
+        assert_eq!(report(&current, Some(&baseline), 60.0), 0);
+        assert_eq!(report(&current, None, 10.0), 0);
+    }
+
+    #[test]
+    fn it_round_trips_reports_as_json() {
+        let report = Report {
+            iterations: 2,
+            records: vec![record("part 2", 42)],
+        };
+        let json = serde_json::to_string(&report).unwrap();
+
+        assert_eq!(serde_json::from_str::<Report>(&json).unwrap(), report);
+    }
+}
@@ -0,0 +1,107 @@
+use std::convert::TryFrom;
+
+use serde::Serialize;
+use serde_json::Value;
+use shared::{Answer, PartResult};
+
+/// One solved part, as emitted by `--format json`.
+#[derive(Debug, PartialEq, Serialize)]
+pub struct Record {
+    pub day: u8,
+    pub part: u8,
+    pub answer: Value,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    pub elapsed_ns: u64,
+}
+
+impl Record {
+    pub fn new(day: u8, result: &PartResult) -> Record {
+        Record {
+            day,
+            part: result.part.number(),
+            answer: match &result.answer {
+                Ok(Some(answer)) => answer_value(answer),
+                Ok(None) | Err(_) => Value::Null,
+            },
+            error: result.answer.as_ref().err().cloned(),
+            elapsed_ns: result.elapsed.as_nanos() as u64,
+        }
+    }
+}
+
+/// Integers stay JSON numbers unless they do not fit in 64 bits, in which case they are
+/// written as strings so that no precision is lost.
+fn answer_value(answer: &Answer) -> Value {
+    match answer {
+        Answer::Integer(answer) => {
+            if let Ok(answer) = i64::try_from(*answer) {
+                Value::from(answer)
+            } else if let Ok(answer) = u64::try_from(*answer) {
+                Value::from(answer)
+            } else {
+                Value::from(answer.to_string())
+            }
+        }
+        Answer::Text(answer) => Value::from(answer.as_str()),
+    }
+}
+
+pub fn print(records: &[Record]) {
+    match serde_json::to_string_pretty(records) {
+        Ok(json) => println!("{}", json),
+        Err(err) => eprintln!("Error: {}", err),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use shared::Part;
+    use std::time::Duration;
+
+    #[test]
+    fn it_serializes_part_results() {
+        let result = PartResult {
+            part: Part::Two,
+            answer: Ok(Some(Answer::from(1_322_306_994_176_usize))),
+            elapsed: Duration::from_nanos(1500),
+        };
+
+        assert_eq!(
+            serde_json::to_string(&Record::new(10, &result)).unwrap(),
+            r#"{"day":10,"part":2,"answer":1322306994176,"elapsed_ns":1500}"#
+        );
+    }
+
+    #[test]
+    fn it_keeps_answers_structured() {
+        assert_eq!(answer_value(&Answer::from(-3_isize)), Value::from(-3));
+        assert_eq!(answer_value(&Answer::from("abc")), Value::from("abc"));
+        assert_eq!(
+            answer_value(&Answer::Integer(i128::MAX)),
+            Value::from(i128::MAX.to_string())
+        );
+
+        let unanswered = PartResult {
+            part: Part::One,
+            answer: Ok(None),
+            elapsed: Duration::default(),
+        };
+        assert_eq!(Record::new(13, &unanswered).answer, Value::Null);
+    }
+
+    #[test]
+    fn it_serializes_failed_parts() {
+        let result = PartResult {
+            part: Part::Two,
+            answer: Err("product of 3000000000 * 10 overflows".to_string()),
+            elapsed: Duration::from_nanos(20),
+        };
+
+        assert_eq!(
+            serde_json::to_string(&Record::new(1, &result)).unwrap(),
+            r#"{"day":1,"part":2,"answer":null,"error":"product of 3000000000 * 10 overflows","elapsed_ns":20}"#
+        );
+    }
+}
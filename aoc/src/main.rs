@@ -0,0 +1,363 @@
+use std::env;
+use std::process;
+
+use shared::{InputSource, Part, Puzzle, RunError};
+
+mod bench;
+mod json;
+mod verify;
+
+use verify::Manifest;
+
+const USAGE: &str = "Usage:
+    aoc <day> [--part N] [--input PATH | --input -] [--format text|json]
+    aoc all [--part N] [--format text|json]
+    aoc verify [--answers PATH]
+    aoc bench [<day> | all] [--iterations N] [--output PATH] [--baseline PATH] [--threshold PCT]";
+
+const DEFAULT_ANSWERS: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/../answers.toml");
+const DEFAULT_BENCH_OUTPUT: &str = "bench.json";
+
+fn registry() -> Vec<Box<dyn Puzzle>> {
+    vec![
+        Box::new(day_1::Day1),
+        Box::new(day_2::Day2),
+        Box::new(day_3::Day3),
+        Box::new(day_4::Day4),
+        Box::new(day_5::Day5),
+        Box::new(day_6::Day6),
+        Box::new(day_7::Day7),
+        Box::new(day_8::Day8),
+        Box::new(day_9::Day9),
+        Box::new(day_10::Day10),
+        Box::new(day_11::Day11),
+        Box::new(day_12::Day12),
+        Box::new(day_13::Day13),
+        Box::new(day_14::Day14),
+        Box::new(day_15::Day15),
+    ]
+}
+
+#[derive(Debug, PartialEq)]
+enum Target {
+    All,
+    Day(u8),
+    Verify,
+    Bench(Option<u8>),
+}
+
+#[derive(Debug, PartialEq)]
+enum Format {
+    Text,
+    Json,
+}
+
+#[derive(Debug, PartialEq)]
+struct Options {
+    target: Target,
+    parts: Vec<Part>,
+    format: Format,
+    input: Option<String>,
+    answers: Option<String>,
+    iterations: usize,
+    output: Option<String>,
+    baseline: Option<String>,
+    threshold: f64,
+}
+
+fn parse_day(day: &str) -> Result<u8, String> {
+    day.parse::<u8>()
+        .map_err(|_| format!("invalid day {:?}", day))
+}
+
+fn parse_args(args: &[String]) -> Result<Options, String> {
+    let mut parts = Part::ALL.to_vec();
+    let mut format = None;
+    let mut input = None;
+    let mut answers = None;
+    let mut iterations = None;
+    let mut output = None;
+    let mut baseline = None;
+    let mut threshold = None;
+
+    let positionals = shared::parse_flags(args, |flag, args| {
+        match flag {
+            "--part" => {
+                let value = args.value(flag, "a value")?;
+                let part = value
+                    .parse::<u8>()
+                    .ok()
+                    .and_then(Part::from_number)
+                    .ok_or(format!("invalid part {:?}, expected 1 or 2", value))?;
+                parts = vec![part];
+            }
+            "--format" => {
+                let value = args.value(flag, "a value")?;
+                format = match value {
+                    "text" => Some(Format::Text),
+                    "json" => Some(Format::Json),
+                    _ => return Err(format!("invalid format {:?}, expected text or json", value)),
+                };
+            }
+            "--input" => input = Some(args.value(flag, "a path")?.to_string()),
+            "--answers" => answers = Some(args.value(flag, "a path")?.to_string()),
+            "--iterations" => {
+                let value = args.value(flag, "a value")?;
+                let value = value
+                    .parse::<usize>()
+                    .ok()
+                    .filter(|n| *n > 0)
+                    .ok_or(format!("invalid iterations {:?}", value))?;
+                iterations = Some(value);
+            }
+            "--output" => output = Some(args.value(flag, "a path")?.to_string()),
+            "--baseline" => baseline = Some(args.value(flag, "a path")?.to_string()),
+            "--threshold" => {
+                let value = args.value(flag, "a value")?;
+                let value = value
+                    .parse::<f64>()
+                    .map_err(|_| format!("invalid threshold {:?}", value))?;
+                threshold = Some(value);
+            }
+            _ => return Ok(false),
+        }
+
+        Ok(true)
+    })?;
+
+    let target = match positionals.as_slice() {
+        [] => return Err("missing day".to_string()),
+        ["all"] => Target::All,
+        ["verify"] => Target::Verify,
+        ["bench"] | ["bench", "all"] => Target::Bench(None),
+        ["bench", day] => Target::Bench(Some(parse_day(day)?)),
+        [day] => Target::Day(parse_day(day)?),
+        [_, other, ..] => return Err(format!("unexpected argument {:?}", other)),
+    };
+
+    if input.is_some() && !matches!(target, Target::Day(_)) {
+        return Err("--input can only be used with a single day".to_string());
+    }
+    if format.is_some() && !matches!(target, Target::All | Target::Day(_)) {
+        return Err("--format can only be used with a day or all".to_string());
+    }
+    if answers.is_some() && target != Target::Verify {
+        return Err("--answers can only be used with verify".to_string());
+    }
+    let is_bench = matches!(target, Target::Bench(_));
+    if !is_bench
+        && (iterations.is_some() || output.is_some() || baseline.is_some() || threshold.is_some())
+    {
+        return Err(
+            "--iterations, --output, --baseline and --threshold can only be used with bench"
+                .to_string(),
+        );
+    }
+
+    Ok(Options {
+        target,
+        parts,
+        format: format.unwrap_or(Format::Text),
+        input,
+        answers,
+        iterations: iterations.unwrap_or(10),
+        output,
+        baseline,
+        threshold: threshold.unwrap_or(10.0),
+    })
+}
+
+fn default_input(puzzle: &dyn Puzzle) -> InputSource {
+    InputSource::resolve(None, puzzle.input_dir())
+}
+
+fn main() {
+    let args = env::args().skip(1).collect::<Vec<String>>();
+    let options = match parse_args(&args) {
+        Ok(options) => options,
+        Err(err) => {
+            eprintln!("Error: {}\n\n{}", err, USAGE);
+            process::exit(2);
+        }
+    };
+
+    let puzzles = registry();
+    match options.target {
+        Target::All | Target::Day(_) => {
+            let selected = match options.target {
+                Target::Day(day) => match puzzles.iter().find(|puzzle| puzzle.day() == day) {
+                    Some(puzzle) => vec![puzzle.as_ref()],
+                    None => {
+                        eprintln!("Error: no solver registered for day {}", day);
+                        process::exit(1);
+                    }
+                },
+                _ => puzzles.iter().map(|puzzle| puzzle.as_ref()).collect(),
+            };
+
+            let mut records = vec![];
+            let mut failed = false;
+            for puzzle in selected {
+                let input = InputSource::resolve(options.input.as_deref(), puzzle.input_dir());
+                let solved = match options.format {
+                    Format::Text => shared::run(puzzle, &input, &options.parts),
+                    Format::Json => input
+                        .read()
+                        .and_then(|lines| puzzle.solve(&lines, &options.parts))
+                        .map_err(RunError::from)
+                        .and_then(|results| {
+                            let day = puzzle.day();
+                            records.extend(results.iter().map(|r| json::Record::new(day, r)));
+                            shared::check_results(&results)
+                        }),
+                };
+
+                if let Err(err) = solved {
+                    eprintln!("Day {}: {}", puzzle.day(), err);
+                    failed = true;
+                }
+            }
+
+            if options.format == Format::Json {
+                json::print(&records);
+            }
+            if failed {
+                process::exit(1);
+            }
+        }
+        Target::Verify => {
+            let answers = options.answers.as_deref().unwrap_or(DEFAULT_ANSWERS);
+            let manifest = match Manifest::load(answers) {
+                Ok(manifest) => manifest,
+                Err(err) => {
+                    eprintln!("Error: {}", err);
+                    process::exit(1);
+                }
+            };
+
+            let checks = verify::verify(&puzzles, &manifest, &default_input);
+            if !verify::report(&checks) {
+                process::exit(1);
+            }
+        }
+        Target::Bench(day) => {
+            let selected = puzzles
+                .iter()
+                .filter(|puzzle| day.is_none_or(|day| puzzle.day() == day))
+                .map(|puzzle| puzzle.as_ref())
+                .collect::<Vec<&dyn Puzzle>>();
+            if selected.is_empty() {
+                eprintln!("Error: no solver registered for day {}", day.unwrap_or(0));
+                process::exit(1);
+            }
+
+            let baseline = options.baseline.as_deref().map(|baseline| {
+                bench::Report::load(baseline).unwrap_or_else(|err| {
+                    eprintln!("Error: {}", err);
+                    process::exit(1);
+                })
+            });
+
+            let report = bench::bench(&selected, options.iterations, &default_input);
+            let output = options.output.as_deref().unwrap_or(DEFAULT_BENCH_OUTPUT);
+            if let Err(err) = report.save(output) {
+                eprintln!("Error: {}", err);
+                process::exit(1);
+            }
+
+            let regressions = bench::report(&report, baseline.as_ref(), options.threshold);
+            println!("Results written to {}", output);
+            if regressions > 0 {
+                process::exit(1);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use shared::split_args as args;
+
+    #[test]
+    fn it_parses_a_single_day() {
+        let options = parse_args(&args("7 --part 2 --input other.txt")).unwrap();
+        assert_eq!(options.target, Target::Day(7));
+        assert_eq!(options.parts, vec![Part::Two]);
+        assert_eq!(options.input, Some("other.txt".to_string()));
+        assert_eq!(options.format, Format::Text);
+    }
+
+    #[test]
+    fn it_parses_the_output_format() {
+        let options = parse_args(&args("all --format json")).unwrap();
+        assert_eq!(options.target, Target::All);
+        assert_eq!(options.format, Format::Json);
+
+        assert_eq!(
+            parse_args(&args("3 --format text")).unwrap().format,
+            Format::Text
+        );
+        assert!(parse_args(&args("3 --format yaml")).is_err());
+        assert!(parse_args(&args("verify --format json")).is_err());
+    }
+
+    #[test]
+    fn it_parses_all_days() {
+        let options = parse_args(&args("all")).unwrap();
+        assert_eq!(options.target, Target::All);
+        assert_eq!(options.parts, vec![Part::One, Part::Two]);
+        assert_eq!(options.input, None);
+    }
+
+    #[test]
+    fn it_parses_verify() {
+        let options = parse_args(&args("verify --answers other.toml")).unwrap();
+        assert_eq!(options.target, Target::Verify);
+        assert_eq!(options.answers, Some("other.toml".to_string()));
+    }
+
+    #[test]
+    fn it_parses_bench() {
+        let options = parse_args(&args("bench")).unwrap();
+        assert_eq!(options.target, Target::Bench(None));
+        assert_eq!(options.iterations, 10);
+        assert_eq!(options.output, None);
+
+        let options = parse_args(&args(
+            "bench 15 --iterations 3 --output out.json --baseline base.json --threshold 25",
+        ))
+        .unwrap();
+        assert_eq!(options.target, Target::Bench(Some(15)));
+        assert_eq!(options.iterations, 3);
+        assert_eq!(options.output, Some("out.json".to_string()));
+        assert_eq!(options.baseline, Some("base.json".to_string()));
+        assert_eq!(options.threshold, 25.0);
+
+        assert_eq!(
+            parse_args(&args("bench all")).unwrap().target,
+            Target::Bench(None)
+        );
+    }
+
+    #[test]
+    fn it_rejects_invalid_arguments() {
+        assert!(parse_args(&args("")).is_err());
+        assert!(parse_args(&args("x")).is_err());
+        assert!(parse_args(&args("3 --part 3")).is_err());
+        assert!(parse_args(&args("all --input input.txt")).is_err());
+        assert!(parse_args(&args("3 4")).is_err());
+        assert!(parse_args(&args("verify --input input.txt")).is_err());
+        assert!(parse_args(&args("3 --answers answers.toml")).is_err());
+        assert!(parse_args(&args("3 --iterations 5")).is_err());
+        assert!(parse_args(&args("bench --iterations 0")).is_err());
+        assert!(parse_args(&args("bench 1 2")).is_err());
+        assert!(parse_args(&args("1 --unknown")).is_err());
+    }
+
+    #[test]
+    fn it_registers_every_day_once() {
+        let days = registry().iter().map(|p| p.day()).collect::<Vec<u8>>();
+        assert_eq!(days, (1..=15).collect::<Vec<u8>>());
+    }
+}
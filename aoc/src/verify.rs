@@ -0,0 +1,246 @@
+use std::collections::HashMap;
+use std::fs;
+use std::time::Duration;
+
+use shared::{InputSource, Part, Puzzle};
+
+/// Expected answers keyed by day and part, loaded from a TOML file such as:
+///
+/// ```toml
+/// [day_1]
+/// part_1 = 514579
+/// part_2 = "241861950"
+/// ```
+#[derive(Debug, Default)]
+pub struct Manifest {
+    answers: HashMap<(u8, u8), String>,
+}
+
+impl Manifest {
+    pub fn load(filename: &str) -> Result<Manifest, String> {
+        let contents = fs::read_to_string(filename)
+            .map_err(|err| format!("could not read {}: {}", filename, err))?;
+
+        Manifest::parse(&contents).map_err(|err| format!("{}: {}", filename, err))
+    }
+
+    pub fn parse(contents: &str) -> Result<Manifest, String> {
+        let value = contents
+            .parse::<toml::Value>()
+            .map_err(|err| err.to_string())?;
+        let days = value.as_table().ok_or("expected a table of days")?;
+
+        let mut answers = HashMap::new();
+        for (day_key, parts) in days {
+            let day = parse_key(day_key, "day_")?;
+            let parts = parts
+                .as_table()
+                .ok_or(format!("[{}] must be a table of parts", day_key))?;
+
+            for (part_key, answer) in parts {
+                let part = parse_key(part_key, "part_")?;
+                let answer = match answer {
+                    toml::Value::Integer(answer) => answer.to_string(),
+                    toml::Value::String(answer) => answer.to_string(),
+                    other => {
+                        return Err(format!(
+                            "{}.{} must be an integer or a string, got {}",
+                            day_key,
+                            part_key,
+                            other.type_str()
+                        ))
+                    }
+                };
+
+                answers.insert((day, part), answer);
+            }
+        }
+
+        Ok(Manifest { answers })
+    }
+
+    pub fn expected(&self, day: u8, part: Part) -> Option<&str> {
+        self.answers.get(&(day, part.number())).map(|a| a.as_str())
+    }
+}
+
+fn parse_key(key: &str, prefix: &str) -> Result<u8, String> {
+    key.strip_prefix(prefix)
+        .and_then(|number| number.parse::<u8>().ok())
+        .ok_or(format!(
+            "invalid key {:?}, expected {}<number>",
+            key, prefix
+        ))
+}
+
+#[derive(Debug, PartialEq)]
+pub enum Status {
+    Pass,
+    Fail(String),
+    Missing,
+}
+
+impl Status {
+    fn check(expected: Option<&str>, answer: Option<&str>) -> Status {
+        match (expected, answer) {
+            (None, _) => Status::Missing,
+            (Some(expected), Some(answer)) if expected == answer => Status::Pass,
+            (Some(expected), Some(answer)) => {
+                Status::Fail(format!("got {}, expected {}", answer, expected))
+            }
+            (Some(expected), None) => Status::Fail(format!("no answer, expected {}", expected)),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct Check {
+    pub day: u8,
+    pub part: Part,
+    pub answer: Option<String>,
+    pub status: Status,
+    pub elapsed: Duration,
+}
+
+/// Runs every part of every puzzle against its real input and compares the answers with the
+/// manifest. Days whose input cannot be loaded fail all of their parts.
+pub fn verify(
+    puzzles: &[Box<dyn Puzzle>],
+    manifest: &Manifest,
+    input_for: &dyn Fn(&dyn Puzzle) -> InputSource,
+) -> Vec<Check> {
+    let mut checks = vec![];
+
+    for puzzle in puzzles {
+        let day = puzzle.day();
+        let results = input_for(puzzle.as_ref())
+            .read()
+            .and_then(|lines| puzzle.solve(&lines, &Part::ALL));
+
+        match results {
+            Ok(results) => {
+                for result in results {
+                    let expected = manifest.expected(day, result.part);
+                    let (answer, status) = match result.answer {
+                        Ok(answer) => {
+                            let answer = answer.map(|answer| answer.to_string());
+                            let status = Status::check(expected, answer.as_deref());
+                            (answer, status)
+                        }
+                        Err(reason) => (None, Status::Fail(reason)),
+                    };
+                    checks.push(Check {
+                        day,
+                        part: result.part,
+                        answer,
+                        status,
+                        elapsed: result.elapsed,
+                    });
+                }
+            }
+            Err(err) => {
+                for part in Part::ALL.iter() {
+                    checks.push(Check {
+                        day,
+                        part: *part,
+                        answer: None,
+                        status: Status::Fail(err.to_string()),
+                        elapsed: Duration::default(),
+                    });
+                }
+            }
+        }
+    }
+
+    checks
+}
+
+/// Prints one line per check plus a summary. Returns `true` when nothing failed.
+pub fn report(checks: &[Check]) -> bool {
+    let mut passed = 0;
+    let mut failed = 0;
+    let mut missing = 0;
+
+    for check in checks {
+        let (label, detail) = match &check.status {
+            Status::Pass => {
+                passed += 1;
+                ("PASS", check.answer.clone().unwrap_or_default())
+            }
+            Status::Fail(reason) => {
+                failed += 1;
+                ("FAIL", reason.to_string())
+            }
+            Status::Missing => {
+                missing += 1;
+                let answer = check.answer.as_deref().unwrap_or("no answer");
+                ("MISSING", format!("{} (no expected answer)", answer))
+            }
+        };
+
+        println!(
+            "Day {:>2} part {}  {:<7}  {:>10.3}ms  {}",
+            check.day,
+            check.part,
+            label,
+            check.elapsed.as_secs_f64() * 1000.0,
+            detail
+        );
+    }
+
+    println!(
+        "\n{} passed, {} failed, {} missing",
+        passed, failed, missing
+    );
+
+    failed == 0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_parses_the_manifest() {
+        let manifest = Manifest::parse(
+            "
+            [day_1]
+            part_1 = 514579
+            part_2 = \"241861950\"
+
+            [day_13]
+            part_1 = 295
+            ",
+        )
+        .unwrap();
+
+        assert_eq!(manifest.expected(1, Part::One), Some("514579"));
+        assert_eq!(manifest.expected(1, Part::Two), Some("241861950"));
+        assert_eq!(manifest.expected(13, Part::One), Some("295"));
+        assert_eq!(manifest.expected(13, Part::Two), None);
+        assert_eq!(manifest.expected(2, Part::One), None);
+    }
+
+    #[test]
+    fn it_rejects_invalid_manifests() {
+        assert!(Manifest::parse("[day_x]\npart_1 = 1").is_err());
+        assert!(Manifest::parse("[day_1]\npart_one = 1").is_err());
+        assert!(Manifest::parse("[day_1]\npart_1 = 1.5").is_err());
+        assert!(Manifest::parse("day_1 = 1").is_err());
+    }
+
+    #[test]
+    fn it_checks_answers() {
+        assert_eq!(Status::check(Some("12"), Some("12")), Status::Pass);
+        assert_eq!(
+            Status::check(Some("12"), Some("13")),
+            Status::Fail("got 13, expected 12".to_string())
+        );
+        assert_eq!(
+            Status::check(Some("12"), None),
+            Status::Fail("no answer, expected 12".to_string())
+        );
+        assert_eq!(Status::check(None, Some("12")), Status::Missing);
+        assert_eq!(Status::check(None, None), Status::Missing);
+    }
+}
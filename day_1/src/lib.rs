@@ -0,0 +1,555 @@
+use std::cmp::Ordering;
+use std::collections::BTreeMap;
+use std::convert::TryFrom;
+use std::error::Error;
+use std::fmt;
+
+use shared::{InputError, Solver};
+
+/// The sum the expense report entries have to add up to.
+pub const TARGET: i64 = 2020;
+
+/// Integer types an expense report can be searched in. Searches work on `i128` remainders,
+/// so sums of any few `i32` or `i64` expenses are exact, and products are checked so that
+/// overflow is reported instead of silently wrapping in release builds.
+pub trait Expense: Copy + Ord + fmt::Debug + fmt::Display {
+    const ONE: Self;
+
+    fn checked_mul(self, other: Self) -> Option<Self>;
+    fn to_i128(self) -> i128;
+    fn from_i128(value: i128) -> Option<Self>;
+}
+
+macro_rules! expense {
+    ($($t:ty),*) => {
+        $(
+            impl Expense for $t {
+                const ONE: $t = 1;
+
+                fn checked_mul(self, other: $t) -> Option<$t> {
+                    <$t>::checked_mul(self, other)
+                }
+
+                fn to_i128(self) -> i128 {
+                    self as i128
+                }
+
+                fn from_i128(value: i128) -> Option<$t> {
+                    <$t>::try_from(value).ok()
+                }
+            }
+        )*
+    };
+}
+
+expense!(i32, i64, i128);
+
+/// The product of some expenses does not fit in the integer type they are stored in.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Overflow(pub String);
+
+impl fmt::Display for Overflow {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "product of {} overflows", self.0)
+    }
+}
+
+impl Error for Overflow {}
+
+/// What is left of a search target once some expenses are taken. Only `i128` expenses can
+/// take it out of the range of an `i128`, after which it can never be reached again.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Remainder {
+    Exact(i128),
+    Saturated,
+}
+
+impl Remainder {
+    fn exact(self) -> Option<i128> {
+        match self {
+            Remainder::Exact(remainder) => Some(remainder),
+            Remainder::Saturated => None,
+        }
+    }
+
+    /// The remainder once `taken` copies of `value` are taken.
+    fn minus<T: Expense>(self, value: T, taken: usize) -> Remainder {
+        self.exact()
+            .zip(i128::try_from(taken).ok())
+            .and_then(|(remainder, taken)| {
+                value
+                    .to_i128()
+                    .checked_mul(taken)
+                    .and_then(|sum| remainder.checked_sub(sum))
+            })
+            .map_or(Remainder::Saturated, Remainder::Exact)
+    }
+}
+
+/// An expense picked by `find_k_sum`: its index in the report and its value.
+pub type Entry<T> = (usize, T);
+
+/// The expense report as a multiset: every distinct value with the indices it appears at, in
+/// ascending order of value.
+///
+/// The remaining target is carried as an `i128`, so partial sums may leave the range of `T`
+/// as long as the whole combination adds up to the target. For `i128` expenses a remainder
+/// that does not fit in an `i128` saturates, and a saturated remainder never matches.
+pub struct Multiset<T> {
+    values: Vec<(T, Vec<usize>)>,
+}
+
+impl<T: Expense> Multiset<T> {
+    pub fn new(values: &[T]) -> Multiset<T> {
+        let mut indices: BTreeMap<T, Vec<usize>> = BTreeMap::new();
+        for (i, value) in values.iter().enumerate() {
+            indices.entry(*value).or_default().push(i);
+        }
+
+        Multiset {
+            values: indices.into_iter().collect(),
+        }
+    }
+
+    pub fn count(&self, value: T) -> usize {
+        self.position(value)
+            .map_or(0, |position| self.values[position].1.len())
+    }
+
+    fn position(&self, value: T) -> Option<usize> {
+        self.values.binary_search_by_key(&value, |(v, _)| *v).ok()
+    }
+
+    /// Finds `k` entries adding up to `target`, ordered by value. An entry is never used more
+    /// often than its value occurs in the report.
+    ///
+    /// Pairs are found with a two-pointer sweep over the distinct values, larger `k` fix their
+    /// smallest entry and recurse on the values from there on.
+    pub fn find_k_sum(&self, k: usize, target: T) -> Option<Vec<Entry<T>>> {
+        self.find_from(0, 0, k, Remainder::Exact(target.to_i128()))
+    }
+
+    /// Entries still available at `position`, when `used` of the entries at `start` are taken.
+    fn available(&self, start: usize, used: usize, position: usize) -> &[usize] {
+        let indices = &self.values[position].1;
+
+        if position == start {
+            &indices[used.min(indices.len())..]
+        } else {
+            indices
+        }
+    }
+
+    fn find_from(
+        &self,
+        start: usize,
+        used: usize,
+        k: usize,
+        target: Remainder,
+    ) -> Option<Vec<Entry<T>>> {
+        match k {
+            0 if target == Remainder::Exact(0) => Some(vec![]),
+            0 => None,
+            1 => {
+                let value = target.exact().and_then(T::from_i128)?;
+                let position = self.position(value).filter(|p| *p >= start)?;
+                let i = *self.available(start, used, position).first()?;
+
+                Some(vec![(i, value)])
+            }
+            2 => self.find_pair_from(start, used, target.exact()?),
+            _ => (start..self.values.len()).find_map(|position| {
+                let i = *self.available(start, used, position).first()?;
+                let value = self.values[position].0;
+                let used = if position == start { used + 1 } else { 1 };
+
+                let mut rest = self.find_from(position, used, k - 1, target.minus(value, 1))?;
+                rest.insert(0, (i, value));
+
+                Some(rest)
+            }),
+        }
+    }
+
+    fn find_pair_from(&self, start: usize, used: usize, target: i128) -> Option<Vec<Entry<T>>> {
+        if start >= self.values.len() {
+            return None;
+        }
+
+        let mut low = start;
+        let mut high = self.values.len() - 1;
+        while low <= high {
+            let low_value = self.values[low].0;
+            let high_value = self.values[high].0;
+            // A sum that overflows lies beyond every `i128` in the direction of its terms
+            let ordering = match low_value.to_i128().checked_add(high_value.to_i128()) {
+                Some(sum) => sum.cmp(&target),
+                None if low_value.to_i128() < 0 => Ordering::Less,
+                None => Ordering::Greater,
+            };
+
+            if ordering == Ordering::Equal {
+                let low_indices = self.available(start, used, low);
+                if low == high {
+                    // Pairing a value with itself needs two occurrences of it
+                    return match low_indices {
+                        [first, second, ..] => {
+                            Some(vec![(*first, low_value), (*second, high_value)])
+                        }
+                        _ => None,
+                    };
+                }
+
+                match (
+                    low_indices.first(),
+                    self.available(start, used, high).first(),
+                ) {
+                    (Some(first), Some(second)) => {
+                        return Some(vec![(*first, low_value), (*second, high_value)])
+                    }
+                    (None, _) => low += 1,
+                    (_, None) => high -= 1,
+                }
+            } else if ordering == Ordering::Less {
+                low += 1;
+            } else if high == 0 {
+                break;
+            } else {
+                high -= 1;
+            }
+        }
+
+        None
+    }
+
+    /// Every distinct combination of `k` entries, told apart by their index in the report,
+    /// that adds up to `target`. Entries within a combination are ordered by index.
+    pub fn combinations(&self, k: usize, target: T) -> impl Iterator<Item = Vec<Entry<T>>> + '_ {
+        let mut value_combinations = vec![];
+        let target = Remainder::Exact(target.to_i128());
+        self.value_combinations(0, k, target, &mut vec![], &mut value_combinations);
+
+        value_combinations
+            .into_iter()
+            .flat_map(move |combination| self.expand(&combination))
+    }
+
+    /// Counts what `combinations` would yield without building any of them.
+    pub fn count_combinations(&self, k: usize, target: T) -> u64 {
+        let mut value_combinations = vec![];
+        let target = Remainder::Exact(target.to_i128());
+        self.value_combinations(0, k, target, &mut vec![], &mut value_combinations);
+
+        value_combinations
+            .iter()
+            .map(|combination| {
+                combination
+                    .iter()
+                    .map(|(position, taken)| binomial(self.values[*position].1.len(), *taken))
+                    .product::<u64>()
+            })
+            .sum()
+    }
+
+    /// Collects the combinations of distinct values, as `(position, times taken)`, that add up
+    /// to `target` using values from `start` on.
+    fn value_combinations(
+        &self,
+        start: usize,
+        k: usize,
+        target: Remainder,
+        current: &mut Vec<(usize, usize)>,
+        found: &mut Vec<Vec<(usize, usize)>>,
+    ) {
+        match k {
+            0 => {
+                if target == Remainder::Exact(0) {
+                    found.push(current.clone());
+                }
+            }
+            1 => {
+                let value = target.exact().and_then(T::from_i128);
+                if let Some(position) = value
+                    .and_then(|value| self.position(value))
+                    .filter(|p| *p >= start)
+                {
+                    current.push((position, 1));
+                    found.push(current.clone());
+                    current.pop();
+                }
+            }
+            _ => {
+                for position in start..self.values.len() {
+                    let (value, indices) = &self.values[position];
+
+                    for taken in 1..=indices.len().min(k) {
+                        current.push((position, taken));
+                        self.value_combinations(
+                            position + 1,
+                            k - taken,
+                            target.minus(*value, taken),
+                            current,
+                            found,
+                        );
+                        current.pop();
+                    }
+                }
+            }
+        }
+    }
+
+    /// Every choice of entries for a combination of values.
+    fn expand(&self, combination: &[(usize, usize)]) -> Vec<Vec<Entry<T>>> {
+        combination
+            .iter()
+            .fold(vec![vec![]], |partial, (position, taken)| {
+                let (value, indices) = &self.values[*position];
+                let choices = index_combinations(indices, *taken);
+
+                partial
+                    .iter()
+                    .flat_map(|entries| {
+                        choices.iter().map(move |choice| {
+                            let mut entries: Vec<Entry<T>> = entries.clone();
+                            entries.extend(choice.iter().map(|i| (*i, *value)));
+                            entries
+                        })
+                    })
+                    .collect()
+            })
+            .into_iter()
+            .map(|mut entries| {
+                entries.sort_unstable();
+                entries
+            })
+            .collect()
+    }
+}
+
+/// All ways of picking `taken` of the `indices`, keeping their order.
+fn index_combinations(indices: &[usize], taken: usize) -> Vec<Vec<usize>> {
+    if taken == 0 {
+        return vec![vec![]];
+    }
+
+    (0..indices.len())
+        .flat_map(|i| {
+            index_combinations(&indices[i + 1..], taken - 1)
+                .into_iter()
+                .map(move |mut rest| {
+                    rest.insert(0, indices[i]);
+                    rest
+                })
+        })
+        .collect()
+}
+
+fn binomial(n: usize, k: usize) -> u64 {
+    if k > n {
+        return 0;
+    }
+
+    (0..k as u64).fold(1, |result, i| result * (n as u64 - i) / (i + 1))
+}
+
+/// Finds `k` entries of `values` that add up to `target`, ordered by value.
+pub fn find_k_sum<T: Expense>(values: &[T], k: usize, target: T) -> Option<Vec<Entry<T>>> {
+    Multiset::new(values).find_k_sum(k, target)
+}
+
+pub fn product<T: Expense>(entries: &[Entry<T>]) -> Result<T, Overflow> {
+    entries
+        .iter()
+        .try_fold(T::ONE, |product, (_, value)| product.checked_mul(*value))
+        .ok_or_else(|| {
+            let values = entries
+                .iter()
+                .map(|(_, value)| value.to_string())
+                .collect::<Vec<String>>();
+
+            Overflow(values.join(" * "))
+        })
+}
+
+pub struct Day1;
+
+impl Solver for Day1 {
+    type Input = Vec<i64>;
+    type Answer = Result<i64, Overflow>;
+
+    fn day(&self) -> u8 {
+        1
+    }
+
+    fn input_dir(&self) -> &'static str {
+        env!("CARGO_MANIFEST_DIR")
+    }
+
+    fn parse(&self, lines: &[String]) -> Result<Vec<i64>, InputError> {
+        shared::parse_lines(lines)
+    }
+
+    fn part_one(&self, expenses: &Vec<i64>) -> Option<Result<i64, Overflow>> {
+        find_k_sum(expenses, 2, TARGET).map(|entries| product(&entries))
+    }
+
+    fn part_two(&self, expenses: &Vec<i64>) -> Option<Result<i64, Overflow>> {
+        find_k_sum(expenses, 3, TARGET).map(|entries| product(&entries))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_sums_number_pair() {
+        let test_numbers = vec![1721, 979, 366, 299, 675, 1456];
+        let entries = find_k_sum(&test_numbers, 2, 2020).unwrap();
+        assert_eq!(entries, vec![(3, 299), (0, 1721)]);
+        assert_eq!(product(&entries), Ok(514579));
+    }
+
+    #[test]
+    fn it_sums_3_number_sequence() {
+        let test_numbers = vec![1721, 979, 366, 299, 675, 1456];
+        let entries = find_k_sum(&test_numbers, 3, 2020).unwrap();
+        assert_eq!(entries, vec![(2, 366), (4, 675), (1, 979)]);
+        assert_eq!(product(&entries), Ok(241861950))
+    }
+
+    #[test]
+    fn it_handles_duplicate_expenses() {
+        let expenses = Multiset::new(&[1010, 5, 1010, 5, 2010]);
+        assert_eq!(expenses.count(1010), 2);
+        assert_eq!(expenses.count(5), 2);
+        assert_eq!(expenses.count(7), 0);
+
+        assert_eq!(
+            expenses.find_k_sum(2, 2020),
+            Some(vec![(0, 1010), (2, 1010)])
+        );
+        assert_eq!(
+            expenses.find_k_sum(3, 2020),
+            Some(vec![(1, 5), (3, 5), (4, 2010)])
+        );
+        assert_eq!(
+            expenses.find_k_sum(4, 2030),
+            Some(vec![(1, 5), (3, 5), (0, 1010), (2, 1010)])
+        );
+    }
+
+    #[test]
+    fn it_does_not_reuse_an_expense() {
+        assert_eq!(find_k_sum(&[1010, 500], 2, 2020), None);
+        assert_eq!(find_k_sum(&[1000, 20], 3, 2020), None);
+        assert_eq!(
+            find_k_sum(&[1000, 1000, 20], 3, 2020).map(|e| e.len()),
+            Some(3)
+        );
+        assert_eq!(find_k_sum(&[2020], 2, 4040), None);
+        assert_eq!(find_k_sum(&[], 2, 0), None);
+    }
+
+    #[test]
+    fn it_enumerates_every_combination() {
+        let expenses = Multiset::new(&[1010, 5, 1010, 1010, 2015, 5]);
+        let pairs = expenses
+            .combinations(2, 2020)
+            .collect::<Vec<Vec<Entry<i32>>>>();
+
+        assert_eq!(
+            pairs,
+            vec![
+                vec![(1, 5), (4, 2015)],
+                vec![(4, 2015), (5, 5)],
+                vec![(0, 1010), (2, 1010)],
+                vec![(0, 1010), (3, 1010)],
+                vec![(2, 1010), (3, 1010)],
+            ]
+        );
+        assert_eq!(expenses.count_combinations(2, 2020), 5);
+
+        assert_eq!(expenses.combinations(3, 2025).count(), 1 + 3 * 2);
+        assert_eq!(expenses.count_combinations(3, 2025), 7);
+        assert_eq!(expenses.count_combinations(3, 3030), 1 + 2 * 3);
+        assert_eq!(expenses.count_combinations(2, 1), 0);
+    }
+
+    #[test]
+    fn it_reports_overflowing_products() {
+        let large = vec![1_500_000_i32, 2_000_000, 500_000];
+        let entries = find_k_sum(&large, 3, 4_000_000).unwrap();
+        assert_eq!(
+            product(&entries),
+            Err(Overflow("500000 * 1500000 * 2000000".to_string()))
+        );
+
+        let large = large.iter().map(|v| *v as i64).collect::<Vec<i64>>();
+        let entries = find_k_sum(&large, 3, 4_000_000).unwrap();
+        assert_eq!(product(&entries), Ok(1_500_000_000_000_000_000));
+
+        let huge = vec![i64::MAX / 2, 4, i64::MAX / 3];
+        let entries = find_k_sum(&huge, 2, i64::MAX / 2 + 4).unwrap();
+        assert!(product(&entries).is_err());
+
+        let huge = huge.iter().map(|v| *v as i128).collect::<Vec<i128>>();
+        let entries = find_k_sum(&huge, 2, (i64::MAX / 2 + 4) as i128).unwrap();
+        assert_eq!(product(&entries), Ok((i64::MAX / 2) as i128 * 4));
+    }
+
+    #[test]
+    fn it_does_not_match_overflowing_sums() {
+        let values = vec![i32::MAX, i32::MAX - 1, 1, i32::MIN, -1];
+        assert_eq!(
+            find_k_sum(&values, 2, i32::MAX),
+            Some(vec![(2, 1), (1, i32::MAX - 1)])
+        );
+        assert_eq!(find_k_sum(&values, 2, i32::MIN), None);
+        assert_eq!(
+            find_k_sum(&values, 3, i32::MAX - 1),
+            Some(vec![(4, -1), (2, 1), (1, i32::MAX - 1)])
+        );
+        let expenses = Multiset::new(&values);
+        assert_eq!(expenses.count_combinations(3, i32::MIN), 1);
+        assert_eq!(expenses.count_combinations(2, -2), 1);
+        assert_eq!(expenses.count_combinations(2, -1), 1);
+        assert_eq!(expenses.count_combinations(2, i32::MAX - 1), 1);
+        // The partial sum MAX + MIN is negative, but the whole triple fits
+        assert_eq!(expenses.count_combinations(3, i32::MAX - 2), 1);
+    }
+
+    #[test]
+    fn it_finds_sums_whose_partial_remainders_overflow() {
+        let values = vec![-5, i32::MAX - 2, 7];
+        assert_eq!(
+            find_k_sum(&values, 3, i32::MAX),
+            Some(vec![(0, -5), (2, 7), (1, i32::MAX - 2)])
+        );
+        assert_eq!(Multiset::new(&values).count_combinations(3, i32::MAX), 1);
+    }
+
+    #[test]
+    fn it_fails_parts_whose_product_overflows() {
+        let expenses = vec![3_000_000_000, -2_999_997_990, 10];
+        assert_eq!(
+            Day1.part_two(&expenses),
+            Some(Err(Overflow("-2999997990 * 10 * 3000000000".to_string())))
+        );
+        assert_eq!(Day1.part_one(&expenses), None);
+    }
+
+    #[test]
+    fn it_sums_any_number_of_entries() {
+        let test_numbers = vec![1721, 979, 366, 299, 675, 1456];
+        let entries = find_k_sum(&test_numbers, 4, 366 + 299 + 675 + 1456).unwrap();
+        assert_eq!(
+            entries.iter().map(|(i, _)| *i).collect::<Vec<usize>>(),
+            vec![3, 2, 4, 5]
+        );
+
+        assert_eq!(find_k_sum(&test_numbers, 1, 979), Some(vec![(1, 979)]));
+        assert_eq!(find_k_sum(&test_numbers, 2, 1), None);
+        assert_eq!(find_k_sum(&test_numbers, 7, 2020), None);
+    }
+}
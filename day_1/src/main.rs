@@ -1,85 +1,107 @@
-use std::collections::HashSet;
-use std::fs::File;
-use std::io::prelude::*;
-use std::iter::FromIterator;
+use std::env;
+use std::process;
 
-fn find_matching_number_pair(arr: &Vec<i32>, desired_sum: i32) -> Option<i32> {
-    let mut sorted = arr.clone();
-    sorted.sort();
+use day_1::{Day1, Multiset, TARGET};
+use shared::{InputSource, Part, Puzzle};
 
-    let mut reverse_sorted = sorted.clone();
-    reverse_sorted.reverse();
+const USAGE: &str = "Usage: day_1 [INPUT] [--list K | --count K]";
 
-    for expense_low in sorted.iter() {
-        for expense_high in reverse_sorted.iter() {
-            if expense_high + expense_low == desired_sum {
-                return Some(expense_high * expense_low);
-            }
-        }
-    }
-    None
+/// What to do with the expense report.
+#[derive(Debug, PartialEq)]
+enum Mode {
+    Solve,
+    List(usize),
+    Count(usize),
 }
 
-fn find_matching_3_number_sequence(arr: &Vec<i32>, desired_sum: i32) -> Option<i32> {
-    // NOTE: assumption numbers are unique. Holds true for test set but in reality duplicates would
-    // be expected
-    let numbers_set: HashSet<i32> = HashSet::from_iter(arr.iter().cloned());
+fn parse_args(args: &[String]) -> Result<(Option<String>, Mode), String> {
+    let mut mode = Mode::Solve;
 
-    for (i, expense) in arr.iter().enumerate() {
-        for j in i + 1..arr.len() {
-            let expense_2 = arr[j];
+    let input = shared::parse_input_and_flags(args, |flag, args| {
+        match flag {
+            "--list" | "--count" => {
+                let value = args.value(flag, "a value")?;
+                let k = value
+                    .parse::<usize>()
+                    .ok()
+                    .filter(|k| *k > 0)
+                    .ok_or(format!("invalid number of entries {:?}", value))?;
 
-            let remaining = desired_sum - expense - expense_2;
-            if numbers_set.contains(&remaining) {
-                return Some(expense * expense_2 * remaining);
+                mode = if flag == "--list" {
+                    Mode::List(k)
+                } else {
+                    Mode::Count(k)
+                };
             }
+            _ => return Ok(false),
         }
-    }
 
-    None
+        Ok(true)
+    })?;
+
+    Ok((input, mode))
 }
 
 fn main() {
-    let mut file = File::open("input.txt").unwrap();
-    let mut contents = String::new();
+    let args = env::args().skip(1).collect::<Vec<String>>();
+    let (input, mode) = parse_args(&args).unwrap_or_else(|err| {
+        eprintln!("Error: {}\n\n{}", err, USAGE);
+        process::exit(2);
+    });
+    let input = InputSource::resolve(input.as_deref(), Day1.input_dir());
+
+    match mode {
+        Mode::Solve => shared::unwrap_or_exit(shared::run(&Day1, &input, &Part::ALL)),
+        Mode::List(k) => {
+            let expenses =
+                shared::unwrap_or_exit(input.read().and_then(|l| shared::parse_lines(&l)));
+            let multiset = Multiset::new(&expenses);
 
-    file.read_to_string(&mut contents).unwrap();
-    let expenses: Vec<i32> = contents
-        .lines()
-        .map(|line| line.trim().parse::<i32>().unwrap())
-        .collect();
+            let mut count = 0;
+            for combination in multiset.combinations(k, TARGET) {
+                let entries = combination
+                    .iter()
+                    .map(|(i, value)| format!("line {} ({})", i + 1, value))
+                    .collect::<Vec<String>>();
+                println!("{}", entries.join(" + "));
+                count += 1;
+            }
+            println!(
+                "{} combination(s) of {} entries sum to {}",
+                count, k, TARGET
+            );
+        }
+        Mode::Count(k) => {
+            let expenses =
+                shared::unwrap_or_exit(input.read().and_then(|l| shared::parse_lines(&l)));
+            let count = Multiset::new(&expenses).count_combinations(k, TARGET);
 
-    let desired_sum = 2020;
-    println!("Results");
-    println!(
-        "2 numbers {}",
-        find_matching_number_pair(&expenses, desired_sum).unwrap()
-    );
-    println!(
-        "3 numbers {}",
-        find_matching_3_number_sequence(&expenses, desired_sum).unwrap()
-    );
+            println!(
+                "{} combination(s) of {} entries sum to {}",
+                count, k, TARGET
+            );
+        }
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use shared::split_args as args;
 
     #[test]
-    fn it_sums_number_pair() {
-        let test_numbers = vec![1721, 979, 366, 299, 675, 1456];
+    fn it_parses_the_listing_flags() {
+        assert_eq!(parse_args(&args("")).unwrap(), (None, Mode::Solve));
         assert_eq!(
-            find_matching_number_pair(&test_numbers, 2020).unwrap(),
-            514579
+            parse_args(&args("other.txt --list 3")).unwrap(),
+            (Some("other.txt".to_string()), Mode::List(3))
         );
-    }
-
-    #[test]
-    fn it_sums_3_number_sequence() {
-        let test_numbers = vec![979, 366, 675];
         assert_eq!(
-            find_matching_3_number_sequence(&test_numbers, 2020).unwrap(),
-            241861950
-        )
+            parse_args(&args("--count 2")).unwrap(),
+            (None, Mode::Count(2))
+        );
+        assert!(parse_args(&args("--list")).is_err());
+        assert!(parse_args(&args("--list 0")).is_err());
+        assert!(parse_args(&args("a.txt b.txt")).is_err());
     }
 }
@@ -0,0 +1,140 @@
+use std::error::Error;
+use std::fmt;
+
+use shared::{InputError, Solver};
+
+/// The number of adaptor arrangements does not fit in a `usize`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Overflow;
+
+impl fmt::Display for Overflow {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "the number of adaptor arrangements overflows")
+    }
+}
+
+impl Error for Overflow {}
+
+/// The outlet, every adaptor in ascending order and the device, or `None` when the adaptors
+/// can not all be chained because two neighbours differ by more than 3 jolts (or by nothing).
+fn adaptor_chain(adaptors: &[usize]) -> Option<Vec<usize>> {
+    let outlet_joltage = 0;
+    let device_joltage = adaptors.iter().max()?.checked_add(3)?;
+
+    let mut sorted_adaptors = adaptors.to_vec();
+    sorted_adaptors.sort();
+
+    let mut all_adaptors = vec![outlet_joltage];
+    for adaptor in sorted_adaptors {
+        all_adaptors.push(adaptor)
+    }
+    all_adaptors.push(device_joltage);
+
+    all_adaptors
+        .windows(2)
+        .all(|pair| (1..=3).contains(&(pair[1] - pair[0])))
+        .then_some(all_adaptors)
+}
+
+pub fn calculate_diff_multiplier(adaptors: &[usize]) -> Option<usize> {
+    let all_adaptors = adaptor_chain(adaptors)?;
+
+    let mut one_diff_count = 0;
+    let mut three_diff_count = 0;
+
+    for i in 0..all_adaptors.len() - 1 {
+        if all_adaptors[i] + 1 == all_adaptors[i + 1] {
+            one_diff_count += 1;
+        } else if all_adaptors[i] + 3 == all_adaptors[i + 1] {
+            three_diff_count += 1;
+        }
+    }
+
+    Some(one_diff_count * three_diff_count)
+}
+
+/// The number of ways to connect the outlet to the device, counting for every joltage in the
+/// chain the ways to reach it from the (at most three) joltages within 3 jolts below it.
+pub fn calculate_adapter_configurations(adaptors: &[usize]) -> Option<Result<usize, Overflow>> {
+    let all_adaptors = adaptor_chain(adaptors)?;
+
+    let mut ways: Vec<usize> = vec![1];
+    for i in 1..all_adaptors.len() {
+        let mut count: usize = 0;
+        for j in (0..i)
+            .rev()
+            .take_while(|j| all_adaptors[i] - all_adaptors[*j] <= 3)
+        {
+            count = match count.checked_add(ways[j]) {
+                Some(count) => count,
+                None => return Some(Err(Overflow)),
+            };
+        }
+        ways.push(count);
+    }
+
+    ways.last().copied().map(Ok)
+}
+
+pub fn parse_input(filename: &str) -> Result<Vec<usize>, InputError> {
+    shared::try_parse_lines(filename)
+}
+
+pub struct Day10;
+
+impl Solver for Day10 {
+    type Input = Vec<usize>;
+    type Answer = Result<usize, Overflow>;
+
+    fn day(&self) -> u8 {
+        10
+    }
+
+    fn input_dir(&self) -> &'static str {
+        env!("CARGO_MANIFEST_DIR")
+    }
+
+    fn parse(&self, lines: &[String]) -> Result<Vec<usize>, InputError> {
+        shared::parse_lines(lines)
+    }
+
+    fn part_one(&self, adaptors: &Vec<usize>) -> Option<Result<usize, Overflow>> {
+        calculate_diff_multiplier(adaptors).map(Ok)
+    }
+
+    fn part_two(&self, adaptors: &Vec<usize>) -> Option<Result<usize, Overflow>> {
+        calculate_adapter_configurations(adaptors)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_calculates_the_adaptor_chain() {
+        let adaptors = parse_input("test_input.txt").unwrap();
+        assert_eq!(calculate_diff_multiplier(&adaptors), Some(220));
+    }
+
+    #[test]
+    fn it_calculates_the_different_configurations() {
+        let adaptors = parse_input("test_input.txt").unwrap();
+        assert_eq!(calculate_adapter_configurations(&adaptors), Some(Ok(19208)));
+    }
+
+    #[test]
+    fn it_counts_configurations_with_gaps_of_two() {
+        assert_eq!(calculate_diff_multiplier(&[1, 2, 4]), Some(2));
+        // 0-1-2-4-7, 0-2-4-7, 0-1-4-7
+        assert_eq!(calculate_adapter_configurations(&[1, 2, 4]), Some(Ok(3)));
+    }
+
+    #[test]
+    fn it_has_no_answers_without_an_adaptor_chain() {
+        assert_eq!(Day10.part_one(&vec![]), None);
+        assert_eq!(Day10.part_two(&vec![]), None);
+        assert_eq!(calculate_diff_multiplier(&[1, 5]), None);
+        assert_eq!(calculate_adapter_configurations(&[2, 2]), None);
+    }
+}
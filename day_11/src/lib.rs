@@ -0,0 +1,333 @@
+use std::convert::TryFrom;
+use std::fmt;
+
+use shared::{Cell, Grid, InputError, Solver, UnknownCell, NEIGHBOUR_OFFSETS};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Seat {
+    Floor,
+    Empty,
+    Occupied,
+}
+
+impl TryFrom<char> for Seat {
+    type Error = UnknownCell;
+
+    fn try_from(c: char) -> Result<Seat, UnknownCell> {
+        match c {
+            '.' => Ok(Seat::Floor),
+            'L' => Ok(Seat::Empty),
+            '#' => Ok(Seat::Occupied),
+            other => Err(UnknownCell(other)),
+        }
+    }
+}
+
+impl Cell for Seat {
+    fn to_char(self) -> char {
+        match self {
+            Seat::Floor => '.',
+            Seat::Empty => 'L',
+            Seat::Occupied => '#',
+        }
+    }
+}
+
+impl fmt::Display for Seat {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.to_char())
+    }
+}
+
+pub fn parse_seating_layout(filename: &str) -> Result<Grid<Seat>, InputError> {
+    let lines = shared::try_read_file(filename)?;
+
+    parse_layout(&lines)
+}
+
+pub fn parse_layout(lines: &[String]) -> Result<Grid<Seat>, InputError> {
+    Grid::parse_cells(lines)
+}
+
+fn check_item(item: &Seat, (num_vacant, num_taken): (usize, usize)) -> (usize, usize) {
+    match item {
+        Seat::Empty => (num_vacant + 1, num_taken),
+        Seat::Occupied => (num_vacant, num_taken + 1),
+        Seat::Floor => (num_vacant, num_taken),
+    }
+}
+
+/// Counts `(vacant, taken)` seats around a position in the layout.
+pub type SeatCounter = dyn Fn(&Grid<Seat>, usize, usize) -> (usize, usize);
+
+#[derive(Copy, Clone)]
+pub enum Direction {
+    Increase = 1,
+    Unchanged = 0,
+    Decrease = -1,
+}
+
+fn first_non_empty_seat_from(
+    layout: &Grid<Seat>,
+    start: (usize, usize),
+    offset: (isize, isize),
+) -> (usize, usize) {
+    let mut current = start;
+
+    while let Some(next) = layout.offset(current, offset) {
+        current = next;
+
+        if layout[current] != Seat::Floor {
+            break;
+        }
+    }
+
+    current
+}
+
+pub fn find_first_non_empty_seat_in_direction(
+    layout: &Grid<Seat>,
+    start_row: usize,
+    start_col: usize,
+    row_direction: Direction,
+    col_direction: Direction,
+) -> (usize, usize) {
+    first_non_empty_seat_from(
+        layout,
+        (start_row, start_col),
+        (row_direction as isize, col_direction as isize),
+    )
+}
+
+pub fn check_adjacent_seats(
+    layout: &Grid<Seat>,
+    curr_item_row: usize,
+    curr_item_col: usize,
+) -> (usize, usize) {
+    layout
+        .neighbours((curr_item_row, curr_item_col))
+        .fold((0, 0), |counts, (_, item)| check_item(item, counts))
+}
+
+pub fn check_non_empty_adjacent_seats(
+    layout: &Grid<Seat>,
+    curr_item_row: usize,
+    curr_item_col: usize,
+) -> (usize, usize) {
+    let start = (curr_item_row, curr_item_col);
+
+    NEIGHBOUR_OFFSETS
+        .iter()
+        .map(|offset| first_non_empty_seat_from(layout, start, *offset))
+        // Directions that immediately leave the layout never move away from the start
+        .filter(|position| *position != start)
+        .fold((0, 0), |counts, position| {
+            check_item(&layout[position], counts)
+        })
+}
+
+pub fn update_seating(
+    layout: &Grid<Seat>,
+    check_adjacent_seats: &SeatCounter,
+    num_taken_limit: usize,
+) -> (Grid<Seat>, bool) {
+    let mut has_changed = false;
+    let new_layout = layout.map(|(row, col), item| {
+        let (_num_vacant, num_taken) = check_adjacent_seats(layout, row, col);
+        match item {
+            Seat::Empty if num_taken == 0 => {
+                has_changed = true;
+                Seat::Occupied
+            }
+            Seat::Occupied if num_taken >= num_taken_limit => {
+                has_changed = true;
+                Seat::Empty
+            }
+            Seat::Floor | Seat::Empty | Seat::Occupied => *item,
+        }
+    });
+
+    (new_layout, has_changed)
+}
+
+pub fn count_occupied(layout: &Grid<Seat>) -> usize {
+    layout
+        .iter()
+        .filter(|(_, seat)| **seat == Seat::Occupied)
+        .count()
+}
+
+pub fn stabilize_seating(
+    layout: &Grid<Seat>,
+    check_adjacent_seats: &SeatCounter,
+    num_taken_limit: usize,
+) -> Grid<Seat> {
+    let mut new_layout = layout.clone();
+    loop {
+        let result = update_seating(&new_layout, check_adjacent_seats, num_taken_limit);
+        new_layout = result.0;
+        let has_changed = result.1;
+        if !has_changed {
+            break;
+        }
+    }
+
+    new_layout
+}
+
+pub struct Day11;
+
+impl Solver for Day11 {
+    type Input = Grid<Seat>;
+    type Answer = usize;
+
+    fn day(&self) -> u8 {
+        11
+    }
+
+    fn input_dir(&self) -> &'static str {
+        env!("CARGO_MANIFEST_DIR")
+    }
+
+    fn parse(&self, lines: &[String]) -> Result<Grid<Seat>, InputError> {
+        parse_layout(lines)
+    }
+
+    fn part_one(&self, layout: &Grid<Seat>) -> Option<usize> {
+        let stable_layout = stabilize_seating(layout, &check_adjacent_seats, 4);
+
+        Some(count_occupied(&stable_layout))
+    }
+
+    fn part_two(&self, layout: &Grid<Seat>) -> Option<usize> {
+        let stable_layout = stabilize_seating(layout, &check_non_empty_adjacent_seats, 5);
+
+        Some(count_occupied(&stable_layout))
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::bool_assert_comparison)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_parse_seating_layout() {
+        let layout = parse_seating_layout("test_input.txt").unwrap();
+        assert_eq!(layout.width(), 10, "correct row length");
+        assert_eq!(layout.height(), 10, "correct number of rows");
+        assert_eq!(layout.to_string().lines().next().unwrap(), "L.LL.LL.LL");
+        assert_eq!(layout.to_string().lines().last().unwrap(), "L.LLLLL.LL");
+    }
+
+    #[test]
+    fn it_counts_adjacent_seats() {
+        let layout = parse_seating_layout("test_input.txt").unwrap();
+        assert_eq!(check_adjacent_seats(&layout, 0, 0), (2, 0));
+        assert_eq!(check_adjacent_seats(&layout, 5, 4), (5, 0));
+        assert_eq!(check_adjacent_seats(&layout, 9, 0), (1, 0));
+        assert_eq!(check_adjacent_seats(&layout, 9, 9), (2, 0));
+    }
+
+    #[test]
+    fn it_updates_seating() {
+        let mut layout = parse_seating_layout("test_input.txt").unwrap();
+        for _ in 0..5 {
+            let result = update_seating(&layout, &check_adjacent_seats, 4);
+            layout = result.0;
+            assert_eq!(result.1, true);
+        }
+        let (layout, has_changed) = update_seating(&layout, &check_adjacent_seats, 4);
+
+        let expected_result = vec![
+            "#.#L.L#.##",
+            "#LLL#LL.L#",
+            "L.#.L..#..",
+            "#L##.##.L#",
+            "#.#L.LL.LL",
+            "#.#L#L#.##",
+            "..L.L.....",
+            "#L#L##L#L#",
+            "#.LLLLLL.L",
+            "#.#L#L#.##",
+        ];
+
+        assert_eq!(has_changed, false);
+        assert_eq!(layout.to_string(), expected_result.join("\n"))
+    }
+
+    #[test]
+    fn it_counts_occupied_seats() {
+        let mut layout = parse_seating_layout("test_input.txt").unwrap();
+        for _ in 0..5 {
+            let result = update_seating(&layout, &check_adjacent_seats, 4);
+            layout = result.0;
+            assert_eq!(result.1, true);
+        }
+
+        assert_eq!(count_occupied(&layout), 37);
+    }
+
+    #[test]
+    fn it_counts_occupied_seats_skipping_non_empty() {
+        let mut layout = parse_seating_layout("test_input.txt").unwrap();
+        for _ in 0..6 {
+            let result = update_seating(&layout, &check_non_empty_adjacent_seats, 5);
+            layout = result.0;
+            assert_eq!(result.1, true);
+        }
+
+        assert_eq!(count_occupied(&layout), 26);
+    }
+
+    #[test]
+    fn it_finds_the_first_non_empty_seat_in_the_direction() {
+        let mut layout = parse_layout(&shared::parse_input_to_string_vec(
+            "
+            .......#.
+            ...#.....
+            .#.......
+            .........
+            ..#L....#
+            ....#....
+            .........
+            #........
+            ...#.....",
+        ))
+        .unwrap();
+
+        assert_eq!(
+            find_first_non_empty_seat_in_direction(
+                &layout,
+                4,
+                3,
+                Direction::Decrease,
+                Direction::Decrease
+            ),
+            (2, 1)
+        );
+
+        layout = parse_layout(&shared::parse_input_to_string_vec(
+            "
+            .##.##.
+            #.#.#.#
+            ##...##
+            ...L...
+            ##...##
+            #.#.#.#
+            .##.##.",
+        ))
+        .unwrap();
+
+        assert_eq!(
+            find_first_non_empty_seat_in_direction(
+                &layout,
+                3,
+                3,
+                Direction::Decrease,
+                Direction::Decrease
+            ),
+            (0, 0)
+        )
+    }
+}
@@ -0,0 +1,377 @@
+use shared::{InputError, Solver};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Direction {
+    North,
+    East,
+    South,
+    West,
+}
+
+impl Direction {
+    /// Clockwise, so that turning is a step through the array.
+    pub const ALL: [Direction; 4] = [
+        Direction::North,
+        Direction::East,
+        Direction::South,
+        Direction::West,
+    ];
+
+    /// The direction after `quarter_turns` turns of 90 degrees, clockwise when positive.
+    pub fn turn(self, quarter_turns: isize) -> Direction {
+        Direction::ALL[(self as isize + quarter_turns).rem_euclid(4) as usize]
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Action {
+    Move(Direction, usize),
+    /// Quarter turns, clockwise when positive.
+    Turn(isize),
+    Forward(usize),
+}
+
+impl Action {
+    pub fn parse(action: &str) -> Result<Action, String> {
+        let mut chars = action.chars();
+        let cmd = chars.next().ok_or("empty action")?;
+        let value = chars
+            .as_str()
+            .parse::<usize>()
+            .map_err(|err| format!("invalid value {:?}: {}", chars.as_str(), err))?;
+
+        match cmd {
+            'N' => Ok(Action::Move(Direction::North, value)),
+            'E' => Ok(Action::Move(Direction::East, value)),
+            'S' => Ok(Action::Move(Direction::South, value)),
+            'W' => Ok(Action::Move(Direction::West, value)),
+            'L' | 'R' if value % 90 != 0 => Err(format!(
+                "can only turn by multiples of 90 degrees, got {}",
+                value
+            )),
+            'L' => Ok(Action::Turn(-((value / 90 % 4) as isize))),
+            'R' => Ok(Action::Turn((value / 90 % 4) as isize)),
+            'F' => Ok(Action::Forward(value)),
+            other => Err(format!("unknown command {:?}", other)),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct NavigatorV1 {
+    pub facing_direction: Direction,
+    pub north: usize,
+    pub west: usize,
+    pub east: usize,
+    pub south: usize,
+}
+
+impl NavigatorV1 {
+    pub fn new(facing_direction: Direction) -> NavigatorV1 {
+        NavigatorV1 {
+            facing_direction,
+            north: 0,
+            west: 0,
+            east: 0,
+            south: 0,
+        }
+    }
+    pub fn nav(&mut self, action: Action) {
+        match action {
+            Action::Move(Direction::North, distance) => {
+                let new_south = self.south as isize - distance as isize;
+
+                if new_south > 0 {
+                    self.south = new_south as usize;
+                } else {
+                    self.north += distance - self.south;
+                    self.south = 0;
+                }
+            }
+            Action::Move(Direction::South, distance) => {
+                let new_north = self.north as isize - distance as isize;
+
+                if new_north > 0 {
+                    self.north = new_north as usize;
+                } else {
+                    self.south += distance - self.north;
+                    self.north = 0;
+                }
+            }
+            Action::Move(Direction::East, distance) => {
+                let new_west = self.west as isize - distance as isize;
+
+                if new_west > 0 {
+                    self.west = new_west as usize;
+                } else {
+                    self.east += distance - self.west;
+                    self.west = 0;
+                }
+            }
+            Action::Move(Direction::West, distance) => {
+                let new_east = self.east as isize - distance as isize;
+
+                if new_east > 0 {
+                    self.east = new_east as usize;
+                } else {
+                    self.west += distance - self.east;
+                    self.east = 0;
+                }
+            }
+            Action::Turn(quarter_turns) => {
+                self.facing_direction = self.facing_direction.turn(quarter_turns)
+            }
+            Action::Forward(distance) => self.nav(Action::Move(self.facing_direction, distance)),
+        }
+    }
+
+    pub fn manhattan_distance(&self) -> usize {
+        self.north + self.west + self.east + self.south
+    }
+}
+
+#[derive(Debug)]
+pub struct Point {
+    pub north: usize,
+    pub west: usize,
+    pub east: usize,
+    pub south: usize,
+}
+
+impl Point {
+    pub fn set_direction(&mut self, direction: Direction, value: usize) {
+        match direction {
+            Direction::North => self.north = value,
+            Direction::West => self.west = value,
+            Direction::East => self.east = value,
+            Direction::South => self.south = value,
+        }
+    }
+
+    pub fn get_direction(&self, direction: Direction) -> usize {
+        match direction {
+            Direction::North => self.north,
+            Direction::West => self.west,
+            Direction::East => self.east,
+            Direction::South => self.south,
+        }
+    }
+}
+#[derive(Debug)]
+pub struct NavigatorV2 {
+    pub facing_direction: Direction,
+    pub ship: Point,
+    pub waypoint: Point,
+}
+
+impl NavigatorV2 {
+    pub fn new(facing_direction: Direction) -> NavigatorV2 {
+        let ship = Point {
+            north: 0,
+            west: 0,
+            east: 0,
+            south: 0,
+        };
+
+        let waypoint = Point {
+            north: 1,
+            west: 0,
+            east: 10,
+            south: 0,
+        };
+
+        NavigatorV2 {
+            facing_direction,
+            ship,
+            waypoint,
+        }
+    }
+    fn rotate(&mut self, quarter_turns: isize) {
+        let mut new_waypoint = Point {
+            north: 0,
+            west: 0,
+            east: 0,
+            south: 0,
+        };
+
+        for curr_dir in Direction::ALL.iter() {
+            let curr_value = self.waypoint.get_direction(*curr_dir);
+
+            new_waypoint.set_direction(curr_dir.turn(quarter_turns), curr_value);
+        }
+
+        self.waypoint = new_waypoint;
+    }
+    pub fn nav(&mut self, action: Action) {
+        match action {
+            Action::Move(Direction::North, amount) => {
+                let new_south = self.waypoint.south as isize - amount as isize;
+
+                if new_south > 0 {
+                    self.waypoint.south = new_south as usize;
+                } else {
+                    self.waypoint.north += amount - self.waypoint.south;
+                    self.waypoint.south = 0;
+                }
+            }
+            Action::Move(Direction::South, amount) => {
+                let new_north = self.waypoint.north as isize - amount as isize;
+
+                if new_north > 0 {
+                    self.waypoint.north = new_north as usize;
+                } else {
+                    self.waypoint.south += amount - self.waypoint.north;
+                    self.waypoint.north = 0;
+                }
+            }
+            Action::Move(Direction::East, amount) => {
+                let new_west = self.waypoint.west as isize - amount as isize;
+
+                if new_west > 0 {
+                    self.waypoint.west = new_west as usize;
+                } else {
+                    self.waypoint.east += amount - self.waypoint.west;
+                    self.waypoint.west = 0;
+                }
+            }
+            Action::Move(Direction::West, amount) => {
+                let new_east = self.waypoint.east as isize - amount as isize;
+
+                if new_east > 0 {
+                    self.waypoint.east = new_east as usize;
+                } else {
+                    self.waypoint.west += amount - self.waypoint.east;
+                    self.waypoint.east = 0;
+                }
+            }
+            Action::Turn(quarter_turns) => self.rotate(quarter_turns),
+            Action::Forward(amount) => {
+                self.ship.north += amount * self.waypoint.north;
+                self.ship.south += amount * self.waypoint.south;
+                if self.ship.north >= self.ship.south {
+                    self.ship.north -= self.ship.south;
+                    self.ship.south = 0;
+                } else {
+                    self.ship.south -= self.ship.north;
+                    self.ship.north = 0;
+                }
+
+                self.ship.east += amount * self.waypoint.east;
+                self.ship.west += amount * self.waypoint.west;
+
+                if self.ship.east >= self.ship.west {
+                    self.ship.east -= self.ship.west;
+                    self.ship.west = 0;
+                } else {
+                    self.ship.west -= self.ship.east;
+                    self.ship.east = 0;
+                }
+            }
+        }
+    }
+
+    pub fn manhattan_distance(&self) -> usize {
+        self.ship.north + self.ship.west + self.ship.east + self.ship.south
+    }
+}
+
+pub struct Day12;
+
+impl Solver for Day12 {
+    type Input = Vec<Action>;
+    type Answer = usize;
+
+    fn day(&self) -> u8 {
+        12
+    }
+
+    fn input_dir(&self) -> &'static str {
+        env!("CARGO_MANIFEST_DIR")
+    }
+
+    fn parse(&self, lines: &[String]) -> Result<Vec<Action>, InputError> {
+        lines
+            .iter()
+            .enumerate()
+            .map(|(i, line)| Action::parse(line).map_err(|err| InputError::parse(i + 1, line, err)))
+            .collect()
+    }
+
+    fn part_one(&self, actions: &Vec<Action>) -> Option<usize> {
+        let mut navigator = NavigatorV1::new(Direction::East);
+        for action in actions.iter() {
+            navigator.nav(*action);
+        }
+
+        Some(navigator.manhattan_distance())
+    }
+
+    fn part_two(&self, actions: &Vec<Action>) -> Option<usize> {
+        let mut navigator = NavigatorV2::new(Direction::East);
+        for action in actions.iter() {
+            navigator.nav(*action);
+        }
+
+        Some(navigator.manhattan_distance())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_calculates_manhattan_distance() {
+        let actions = shared::parse_input_to_string_vec(
+            "
+        F10
+        N3
+        F7
+        R90
+        F11",
+        );
+
+        let mut navigator = NavigatorV1::new(Direction::East);
+
+        for action in actions {
+            navigator.nav(Action::parse(&action).unwrap());
+        }
+
+        assert_eq!(navigator.manhattan_distance(), 25);
+    }
+
+    #[test]
+    fn it_calculates_v2_manhattan_distance() {
+        let actions = shared::parse_input_to_string_vec(
+            "
+        F10
+        N3
+        F7
+        R90
+        F11",
+        );
+
+        let mut navigator = NavigatorV2::new(Direction::East);
+
+        for action in actions {
+            navigator.nav(Action::parse(&action).unwrap());
+        }
+
+        assert_eq!(navigator.manhattan_distance(), 286);
+    }
+
+    #[test]
+    fn it_rejects_invalid_actions() {
+        for action in &["", "F", "Fx", "X10", "L45"] {
+            assert!(Action::parse(action).is_err(), "{:?}", action);
+        }
+        assert_eq!(Action::parse("L270"), Ok(Action::Turn(-3)));
+        assert_eq!(Direction::North.turn(-3), Direction::East);
+
+        let lines = vec!["F10".to_string(), "F1x".to_string()];
+        assert_eq!(
+            Day12.parse(&lines).unwrap_err().to_string(),
+            "line 2: could not parse \"F1x\": invalid value \"1x\": invalid digit found in string"
+        );
+    }
+}
@@ -0,0 +1,145 @@
+use shared::{InputError, Solver};
+
+#[derive(Debug)]
+pub struct Timetable {
+    pub timestamp: usize,
+    /// Bus ids in the order of the notes, `None` for buses that are out of service (`x`).
+    pub buses: Vec<Option<usize>>,
+}
+
+impl Timetable {
+    pub fn read_from_file(filename: &str) -> Result<Timetable, InputError> {
+        let contents = shared::try_read_file(filename)?;
+
+        Timetable::parse(&contents)
+    }
+
+    pub fn parse(contents: &[String]) -> Result<Timetable, InputError> {
+        if contents.len() < 2 {
+            return Err(InputError::parse(
+                contents.len() + 1,
+                "",
+                "expected a timestamp line followed by a bus notes line",
+            ));
+        }
+        let timestamp = contents[0]
+            .parse::<usize>()
+            .map_err(|err| InputError::parse(1, &contents[0], err))?;
+        let buses = contents[1]
+            .split(",")
+            .map(|note| match note {
+                "x" => Ok(None),
+                id => match id.parse::<usize>() {
+                    Ok(0) => Err(InputError::parse(2, &contents[1], "bus id 0 is not valid")),
+                    Ok(id) => Ok(Some(id)),
+                    Err(err) => Err(InputError::parse(
+                        2,
+                        &contents[1],
+                        format!("invalid bus id {:?}: {}", id, err),
+                    )),
+                },
+            })
+            .collect::<Result<Vec<Option<usize>>, InputError>>()?;
+
+        Ok(Timetable { timestamp, buses })
+    }
+
+    /// The first departure at or after `timestamp` and the bus taking it, `None` when every
+    /// bus is out of service.
+    pub fn find_earliest_matching_bus(&self) -> Option<(usize, usize)> {
+        self.buses
+            .iter()
+            .flatten()
+            .map(|id| (self.timestamp + (id - self.timestamp % id) % id, *id))
+            .min_by_key(|(departure, _)| *departure)
+    }
+
+    pub fn calc_timing_value(&self, timestamp_numeric: usize, bus_number: usize) -> usize {
+        (timestamp_numeric - self.timestamp) * bus_number
+    }
+}
+
+pub struct Day13;
+
+impl Solver for Day13 {
+    type Input = Timetable;
+    type Answer = usize;
+
+    fn day(&self) -> u8 {
+        13
+    }
+
+    fn input_dir(&self) -> &'static str {
+        env!("CARGO_MANIFEST_DIR")
+    }
+
+    fn parse(&self, lines: &[String]) -> Result<Timetable, InputError> {
+        Timetable::parse(lines)
+    }
+
+    fn part_one(&self, timetable: &Timetable) -> Option<usize> {
+        let (timestamp, bus_number) = timetable.find_earliest_matching_bus()?;
+
+        Some(timetable.calc_timing_value(timestamp, bus_number))
+    }
+
+    fn part_two(&self, _timetable: &Timetable) -> Option<usize> {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_parses_the_time_table() {
+        let timetable = Timetable::read_from_file("test_input.txt").unwrap();
+        println!("{:?}", timetable);
+        assert_eq!(timetable.timestamp, 939);
+        assert_eq!(
+            timetable.buses,
+            vec![
+                Some(7),
+                Some(13),
+                None,
+                None,
+                Some(59),
+                None,
+                Some(31),
+                Some(19)
+            ]
+        );
+    }
+
+    #[test]
+    fn it_finds_the_earliest_matching_bus() {
+        let timetable = Timetable::read_from_file("test_input.txt").unwrap();
+        let (timestamp, bus_number) = timetable.find_earliest_matching_bus().unwrap();
+        assert_eq!(timestamp, 944);
+        assert_eq!(bus_number, 59);
+    }
+
+    #[test]
+    fn it_calculates_timing_value() {
+        let timetable = Timetable::read_from_file("test_input.txt").unwrap();
+        let (timestamp, bus_number) = timetable.find_earliest_matching_bus().unwrap();
+        let timing_value = timetable.calc_timing_value(timestamp, bus_number);
+        assert_eq!(timing_value, 295);
+    }
+
+    #[test]
+    fn it_rejects_invalid_bus_ids() {
+        let lines = |notes: &str| vec!["939".to_string(), notes.to_string()];
+
+        assert_eq!(
+            Timetable::parse(&lines("7,abc")).unwrap_err().to_string(),
+            "line 2: could not parse \"7,abc\": invalid bus id \"abc\": invalid digit found in string"
+        );
+        assert!(Timetable::parse(&lines("7,0")).is_err());
+
+        let timetable = Timetable::parse(&lines("x,x")).unwrap();
+        assert_eq!(timetable.find_earliest_matching_bus(), None);
+        assert_eq!(Day13.part_one(&timetable), None);
+    }
+}
@@ -0,0 +1,301 @@
+use shared::{InputError, Solver};
+use std::collections::HashMap;
+
+pub struct Day14;
+
+impl Solver for Day14 {
+    type Input = Vec<Instruction>;
+    type Answer = usize;
+
+    fn day(&self) -> u8 {
+        14
+    }
+
+    fn input_dir(&self) -> &'static str {
+        env!("CARGO_MANIFEST_DIR")
+    }
+
+    fn parse(&self, lines: &[String]) -> Result<Vec<Instruction>, InputError> {
+        lines
+            .iter()
+            .enumerate()
+            .map(|(i, line)| {
+                Instruction::parse(line).map_err(|err| InputError::parse(i + 1, line, err))
+            })
+            .collect()
+    }
+
+    fn part_one(&self, instructions: &Vec<Instruction>) -> Option<usize> {
+        Some(decode_program(instructions, Version::V1))
+    }
+
+    fn part_two(&self, instructions: &Vec<Instruction>) -> Option<usize> {
+        Some(decode_program(instructions, Version::V2))
+    }
+}
+
+fn parse_to_36bit_binary(value: usize) -> String {
+    let binary_representation = format!("{:036b}", value);
+
+    binary_representation
+}
+
+fn from_binary(binary_representation: &str) -> usize {
+    binary_representation
+        .chars()
+        .fold(0, |memo, bit| memo * 2 + (bit == '1') as usize)
+}
+
+/// Values and memory addresses are 36 bit unsigned integers.
+const BITS: usize = 36;
+
+fn parse_36bit(text: &str, what: &str) -> Result<usize, String> {
+    let value = text
+        .parse::<usize>()
+        .map_err(|err| format!("invalid {} {:?}: {}", what, text, err))?;
+
+    if value >> BITS != 0 {
+        return Err(format!("{} {} does not fit in {} bits", what, value, BITS));
+    }
+
+    Ok(value)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MaskBit {
+    Floating,
+    Zero,
+    One,
+}
+
+/// A bitmask as written in the program, most significant bit first.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Mask(Vec<MaskBit>);
+
+impl Mask {
+    pub fn parse(mask: &str) -> Result<Mask, String> {
+        if mask.chars().count() != BITS {
+            return Err(format!("expected a mask of {} bits", BITS));
+        }
+
+        mask.chars()
+            .map(|bit| match bit {
+                'X' => Ok(MaskBit::Floating),
+                '0' => Ok(MaskBit::Zero),
+                '1' => Ok(MaskBit::One),
+                other => Err(format!("invalid mask bit {:?}", other)),
+            })
+            .collect::<Result<Vec<MaskBit>, String>>()
+            .map(Mask)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Instruction {
+    Mask(Mask),
+    Mem { address: usize, value: usize },
+}
+
+impl Instruction {
+    pub fn parse(line: &str) -> Result<Instruction, String> {
+        let line_items = line.split("=").collect::<Vec<&str>>();
+        if line_items.len() != 2 {
+            return Err("expected '<instruction> = <value>'".to_string());
+        }
+        let instruction = line_items[0].trim();
+        let value = line_items[1].trim();
+
+        if instruction == "mask" {
+            return Mask::parse(value).map(Instruction::Mask);
+        }
+
+        let address = instruction
+            .strip_prefix("mem[")
+            .and_then(|address| address.strip_suffix("]"))
+            .ok_or(format!("unknown instruction {:?}", instruction))?;
+
+        Ok(Instruction::Mem {
+            address: parse_36bit(address, "address")?,
+            value: parse_36bit(value, "value")?,
+        })
+    }
+}
+
+pub fn apply_mask_to_value(mask: &Mask, value: usize) -> String {
+    let binary_value = parse_to_36bit_binary(value);
+
+    mask.0
+        .iter()
+        .zip(binary_value.chars())
+        .map(|(mask_bit, value_bit)| match mask_bit {
+            MaskBit::Floating => value_bit,
+            MaskBit::Zero => '0',
+            MaskBit::One => '1',
+        })
+        .collect::<String>()
+}
+
+#[derive(Debug, Clone, Copy)]
+pub enum Version {
+    V1,
+    V2,
+}
+
+/// Runs the program and sums the values left in memory. Writes before the first mask are
+/// not masked.
+pub fn decode_program(instructions: &[Instruction], version: Version) -> usize {
+    let mut mem_map = HashMap::new();
+    let mut mask = None;
+    for instruction in instructions {
+        match instruction {
+            Instruction::Mask(new_mask) => {
+                mask = Some(new_mask);
+            }
+            Instruction::Mem { address, value } => match (version, mask) {
+                (Version::V1, Some(mask)) => {
+                    mem_map.insert(*address, from_binary(&apply_mask_to_value(mask, *value)));
+                }
+                (Version::V2, Some(mask)) => {
+                    for masked_address in apply_mask_to_memory_address(mask, *address) {
+                        mem_map.insert(from_binary(&masked_address), *value);
+                    }
+                }
+                (_, None) => {
+                    mem_map.insert(*address, *value);
+                }
+            },
+        }
+    }
+
+    mem_map.values().sum()
+}
+
+pub fn apply_mask_to_memory_address(mask: &Mask, memory_address: usize) -> Vec<String> {
+    let binary_memory_address = parse_to_36bit_binary(memory_address);
+
+    let mut masked_binary_addresses = vec![String::from("")];
+    for (mask_bit, address_bit) in mask.0.iter().zip(binary_memory_address.chars()) {
+        masked_binary_addresses =
+            masked_binary_addresses
+                .iter()
+                .fold(vec![], |mut memo, curr_adr| {
+                    match mask_bit {
+                        MaskBit::Floating => {
+                            memo.push(format!("{}{}", curr_adr, 1));
+                            memo.push(format!("{}{}", curr_adr, 0));
+                        }
+                        MaskBit::Zero => {
+                            memo.push(format!("{}{}", curr_adr, address_bit));
+                        }
+                        MaskBit::One => {
+                            memo.push(format!("{}{}", curr_adr, 1));
+                        }
+                    };
+
+                    memo
+                });
+    }
+
+    masked_binary_addresses
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_handles_binary_conversions() {
+        assert_eq!(
+            &parse_to_36bit_binary(11),
+            "000000000000000000000000000000001011"
+        );
+        assert_eq!(from_binary("000000000000000000000000000000001011"), 11);
+
+        assert_eq!(
+            &parse_to_36bit_binary(101),
+            "000000000000000000000000000001100101"
+        );
+
+        assert_eq!(from_binary("000000000000000000000000000001100101"), 101);
+    }
+
+    #[test]
+    fn it_applies_mask() {
+        let mask = Mask::parse("XXXXXXXXXXXXXXXXXXXXXXXXXXXXX1XXXX0X").unwrap();
+        assert_eq!(
+            apply_mask_to_value(&mask, 11),
+            "000000000000000000000000000001001001"
+        );
+
+        assert_eq!(
+            apply_mask_to_value(&mask, 101),
+            "000000000000000000000000000001100101"
+        );
+
+        assert_eq!(
+            apply_mask_to_value(&mask, 0),
+            "000000000000000000000000000001000000"
+        );
+    }
+
+    #[test]
+    fn it_applies_mask_to_memory_address() {
+        let mask = Mask::parse("000000000000000000000000000000X1001X").unwrap();
+        let addresses = apply_mask_to_memory_address(&mask, 42);
+
+        let expected = [
+            "000000000000000000000000000000011010",
+            "000000000000000000000000000000011011",
+            "000000000000000000000000000000111010",
+            "000000000000000000000000000000111011",
+        ];
+
+        for exp in expected.iter() {
+            assert!(addresses.contains(&exp.to_string()))
+        }
+    }
+
+    #[test]
+    fn it_decodes_the_program_instructions() {
+        let instructions = Day14.parse(&shared::read_file("test_input.txt")).unwrap();
+        assert_eq!(decode_program(&instructions, Version::V1), 165);
+    }
+
+    #[test]
+    fn it_decodes_the_program_memory() {
+        let instructions = Day14.parse(&shared::read_file("test_2_input.txt")).unwrap();
+        assert_eq!(decode_program(&instructions, Version::V2), 208);
+    }
+
+    #[test]
+    fn it_rejects_invalid_instructions() {
+        for line in &[
+            "mem[x] = 1",
+            "mem[8] = abc",
+            "mem[8] = 68719476736",
+            "mem[8]",
+            "mask = XX",
+            "mask = XXXXXXXXXXXXXXXXXXXXXXXXXXXXX1XXXX0Z",
+            "add[8] = 1",
+        ] {
+            assert!(Instruction::parse(line).is_err(), "{}", line);
+        }
+
+        let lines = vec!["mem[x] = 1".to_string()];
+        assert_eq!(
+            Day14.parse(&lines).unwrap_err().to_string(),
+            "line 1: could not parse \"mem[x] = 1\": invalid address \"x\": invalid digit found in string"
+        );
+    }
+
+    #[test]
+    fn it_does_not_mask_writes_before_the_first_mask() {
+        let instructions = vec![Instruction::Mem {
+            address: 8,
+            value: 11,
+        }];
+
+        assert_eq!(decode_program(&instructions, Version::V1), 11);
+        assert_eq!(decode_program(&instructions, Version::V2), 11);
+    }
+}
@@ -0,0 +1,121 @@
+use std::collections::HashMap;
+
+use shared::{InputError, Solver};
+
+#[derive(Debug)]
+pub struct SequenceMemory {
+    turn_spoken: HashMap<usize, usize>,
+    pub turn: usize,
+    pub next_num: usize,
+}
+
+impl SequenceMemory {
+    pub fn new(initial_sequence: Vec<usize>) -> SequenceMemory {
+        let mut turn = 1_usize;
+        let mut turn_spoken: HashMap<usize, usize> = HashMap::new();
+        let mut next_num = 0;
+
+        for num in initial_sequence {
+            turn_spoken.insert(num, turn);
+            turn += 1;
+            next_num = num;
+        }
+
+        SequenceMemory {
+            turn,
+            next_num,
+            turn_spoken,
+        }
+    }
+
+    pub fn play_round(&mut self) {
+        let last_turn_num = self.turn - 1;
+        let spoken_num = match self.turn_spoken.get(&self.next_num) {
+            Some(prev_spoken) => last_turn_num - *prev_spoken,
+            _ => 0,
+        };
+
+        self.turn_spoken.insert(self.next_num, last_turn_num);
+        self.turn += 1;
+        self.next_num = spoken_num;
+    }
+
+    pub fn play_rounds(&mut self, num_rounds: usize) -> usize {
+        while self.turn <= num_rounds {
+            self.play_round();
+        }
+
+        self.next_num
+    }
+}
+
+pub struct Day15;
+
+impl Solver for Day15 {
+    type Input = Vec<usize>;
+    type Answer = usize;
+
+    fn day(&self) -> u8 {
+        15
+    }
+
+    fn input_dir(&self) -> &'static str {
+        env!("CARGO_MANIFEST_DIR")
+    }
+
+    fn parse(&self, lines: &[String]) -> Result<Vec<usize>, InputError> {
+        let line = lines.first().map(|l| l.as_str()).unwrap_or("");
+
+        line.split(",")
+            .map(|num| {
+                num.trim()
+                    .parse::<usize>()
+                    .map_err(|err| InputError::parse(1, line, err))
+            })
+            .collect()
+    }
+
+    fn part_one(&self, initial_sequence: &Vec<usize>) -> Option<usize> {
+        let mut seq = SequenceMemory::new(initial_sequence.clone());
+
+        Some(seq.play_rounds(2020))
+    }
+
+    fn part_two(&self, initial_sequence: &Vec<usize>) -> Option<usize> {
+        // TODO: optimized solution
+        let mut seq = SequenceMemory::new(initial_sequence.clone());
+
+        Some(seq.play_rounds(30000000))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_reads_initial_sequence() {
+        let seq = SequenceMemory::new(vec![3, 1, 2]);
+        assert_eq!(seq.turn, 4);
+        assert_eq!(seq.next_num, 2);
+    }
+
+    #[test]
+    fn it_plays_round() {
+        let mut seq = SequenceMemory::new(vec![0, 3, 6]);
+        assert_eq!(seq.turn, 4);
+        seq.play_round();
+        assert_eq!(seq.next_num, 0);
+        assert_eq!(seq.turn, 5);
+        seq.play_round();
+        assert_eq!(seq.next_num, 3);
+        assert_eq!(seq.turn, 6);
+    }
+
+    #[test]
+    fn it_plays_n_rounds() {
+        let mut seq = SequenceMemory::new(vec![0, 3, 6]);
+        let num = seq.play_rounds(10);
+        assert_eq!(num, 0);
+    }
+}
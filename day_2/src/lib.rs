@@ -0,0 +1,414 @@
+use std::error::Error;
+use std::fmt;
+use std::str::FromStr;
+
+use shared::{InputError, Solver};
+use unicode_segmentation::UnicodeSegmentation;
+
+pub mod policy;
+pub mod report;
+
+use policy::{Policy, RangeCount, XorPositions};
+
+/// What positions and counts of a password are measured in. Passwords and requirements are
+/// both split into the same units before they are compared.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Unit {
+  #[default]
+  Chars,
+  Graphemes,
+}
+
+impl Unit {
+  pub fn split(self, text: &str) -> Vec<&str> {
+    match self {
+      Unit::Chars => text
+        .char_indices()
+        .map(|(i, c)| &text[i..i + c.len_utf8()])
+        .collect(),
+      Unit::Graphemes => text.graphemes(true).collect(),
+    }
+  }
+}
+
+/// 0-based unit positions at which `needle` starts in `haystack`, overlapping matches included.
+pub fn occurrences(haystack: &[&str], needle: &[&str]) -> Vec<usize> {
+  if needle.is_empty() {
+    return vec![];
+  }
+
+  haystack
+    .windows(needle.len())
+    .enumerate()
+    .filter(|(_, window)| *window == needle)
+    .map(|(i, _)| i)
+    .collect()
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Password {
+  pub start: usize,
+  pub end: usize,
+  pub requirement: String,
+  pub password: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PasswordError {
+  Format,
+  InvalidNumber(String),
+  EmptyPassword,
+  PositionOutOfRange { position: usize, length: usize },
+}
+
+impl fmt::Display for PasswordError {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    match self {
+      PasswordError::Format => write!(f, "expected \"<start>-<end> <requirement>: <password>\""),
+      PasswordError::InvalidNumber(number) => write!(f, "invalid number {:?}", number),
+      PasswordError::EmptyPassword => write!(f, "missing password"),
+      PasswordError::PositionOutOfRange { position: 0, .. } => {
+        write!(f, "position 0 is out of range, positions start at 1")
+      }
+      PasswordError::PositionOutOfRange { position, length } => write!(
+        f,
+        "position {} is past the end of the {} unit password",
+        position, length
+      ),
+    }
+  }
+}
+
+impl Error for PasswordError {}
+
+fn parse_number(number: &str) -> Result<usize, PasswordError> {
+  number
+    .trim()
+    .parse()
+    .map_err(|_| PasswordError::InvalidNumber(number.trim().to_string()))
+}
+
+impl FromStr for Password {
+  type Err = PasswordError;
+
+  fn from_str(line: &str) -> Result<Password, PasswordError> {
+    let (policy, password) = match line.find(':') {
+      Some(i) => (&line[..i], line[i + 1..].trim()),
+      None => return Err(PasswordError::Format),
+    };
+    let (range, requirement) = match policy.trim().find(' ') {
+      Some(i) => (&policy.trim()[..i], policy.trim()[i + 1..].trim()),
+      None => return Err(PasswordError::Format),
+    };
+    let (start, end) = match range.find('-') {
+      Some(i) => (parse_number(&range[..i])?, parse_number(&range[i + 1..])?),
+      None => return Err(PasswordError::Format),
+    };
+
+    if password.is_empty() {
+      return Err(PasswordError::EmptyPassword);
+    }
+
+    Ok(Password {
+      start,
+      end,
+      requirement: requirement.to_string(),
+      password: password.to_string(),
+    })
+  }
+}
+
+impl Password {
+  pub fn is_valid_old_rule(&self) -> bool {
+    RangeCount.is_valid(self, Unit::Chars)
+  }
+
+  pub fn is_valid_new_rule(&self) -> bool {
+    XorPositions.is_valid(self, Unit::Chars)
+  }
+
+  /// Checks that both numbers are usable as 1-based positions into the password. Only the
+  /// positional policies need this, for the range count they are plain counts.
+  pub fn check_positions(&self, unit: Unit) -> Result<(), PasswordError> {
+    let length = unit.split(&self.password).len();
+
+    for position in [self.start, self.end].iter() {
+      if *position == 0 || *position > length {
+        return Err(PasswordError::PositionOutOfRange {
+          position: *position,
+          length,
+        });
+      }
+    }
+
+    Ok(())
+  }
+}
+
+/// A password line that could not be parsed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LineError {
+  pub line: usize,
+  pub text: String,
+  pub error: PasswordError,
+}
+
+impl fmt::Display for LineError {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    write!(f, "line {}: {} in {:?}", self.line, self.error, self.text)
+  }
+}
+
+/// Every parsed password with its 1-based line number, plus the lines that were rejected.
+#[derive(Debug, Default)]
+pub struct PasswordList {
+  pub passwords: Vec<(usize, Password)>,
+  pub rejected: Vec<LineError>,
+}
+
+/// Parses every line, collecting bad lines instead of stopping at the first one.
+pub fn parse_password_lines(lines: &[String]) -> PasswordList {
+  let mut list = PasswordList::default();
+
+  for (i, line) in lines.iter().enumerate() {
+    match line.parse::<Password>() {
+      Ok(password) => list.passwords.push((i + 1, password)),
+      Err(error) => list.rejected.push(LineError {
+        line: i + 1,
+        text: line.to_string(),
+        error,
+      }),
+    }
+  }
+
+  list
+}
+
+pub fn parse_passwords(lines: &[String]) -> Result<Vec<Password>, InputError> {
+  let list = parse_password_lines(lines);
+
+  match list.rejected.first() {
+    Some(rejected) => Err(InputError::parse(
+      rejected.line,
+      &rejected.text,
+      &rejected.error,
+    )),
+    None => Ok(list.passwords.into_iter().map(|(_, p)| p).collect()),
+  }
+}
+
+pub struct Day2;
+
+impl Solver for Day2 {
+  type Input = Vec<Password>;
+  type Answer = usize;
+
+  fn day(&self) -> u8 {
+    2
+  }
+
+  fn input_dir(&self) -> &'static str {
+    env!("CARGO_MANIFEST_DIR")
+  }
+
+  /// Skips bad lines with a warning, as the day_2 binary does, so that one bad line does not
+  /// stop the runner from counting the rest.
+  fn parse(&self, lines: &[String]) -> Result<Vec<Password>, InputError> {
+    let list = parse_password_lines(lines);
+    for rejected in list.rejected.iter() {
+      eprintln!("Skipping {}", rejected);
+    }
+
+    Ok(list.passwords.into_iter().map(|(_, p)| p).collect())
+  }
+
+  fn part_one(&self, passwords: &Vec<Password>) -> Option<usize> {
+    Some(
+      passwords
+        .iter()
+        .filter(|password| password.is_valid_old_rule())
+        .count(),
+    )
+  }
+
+  fn part_two(&self, passwords: &Vec<Password>) -> Option<usize> {
+    Some(
+      passwords
+        .iter()
+        .filter(|password| password.is_valid_new_rule())
+        .count(),
+    )
+  }
+}
+
+#[cfg(test)]
+#[allow(clippy::bool_assert_comparison)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn it_parses_password_list() {
+    let line = "1-3 a: abcde";
+    let password = line.parse::<Password>().unwrap();
+    assert_eq!(password.start, 1);
+    assert_eq!(password.end, 3);
+    assert_eq!(password.requirement, "a");
+    assert_eq!(password.password, "abcde");
+  }
+
+  #[test]
+  fn it_describes_malformed_lines() {
+    let parse = |line: &str| line.parse::<Password>().unwrap_err().to_string();
+
+    assert_eq!(
+      parse("1-3 a abcde"),
+      "expected \"<start>-<end> <requirement>: <password>\""
+    );
+    assert_eq!(parse("1-x a: abcde"), "invalid number \"x\"");
+    assert_eq!(
+      parse("1-3 : abcde"),
+      "expected \"<start>-<end> <requirement>: <password>\""
+    );
+    assert_eq!(parse("1-3 a:"), "missing password");
+  }
+
+  #[test]
+  fn it_lists_bad_lines_and_keeps_the_rest() {
+    let lines = vec![
+      "1-3 a: abcde".to_string(),
+      "1 a: abcde".to_string(),
+      "2-9 c: ccccccccc".to_string(),
+      "x-3 b: cdefg".to_string(),
+    ];
+    let list = parse_password_lines(&lines);
+
+    assert_eq!(
+      list
+        .passwords
+        .iter()
+        .map(|(line, _)| *line)
+        .collect::<Vec<usize>>(),
+      vec![1, 3]
+    );
+    assert_eq!(
+      list
+        .rejected
+        .iter()
+        .map(|r| r.to_string())
+        .collect::<Vec<String>>(),
+      vec![
+        "line 2: expected \"<start>-<end> <requirement>: <password>\" in \"1 a: abcde\"",
+        "line 4: invalid number \"x\" in \"x-3 b: cdefg\"",
+      ]
+    );
+
+    match parse_passwords(&lines) {
+      Err(InputError::Parse { line, .. }) => assert_eq!(line, 2),
+      other => panic!("expected parse error, got {:?}", other),
+    }
+  }
+
+  #[test]
+  fn it_solves_around_bad_lines() {
+    let lines = vec![
+      "1-3 a: abcde".to_string(),
+      "1 a: abcde".to_string(),
+      "2-9 c: ccccccccc".to_string(),
+    ];
+    let passwords = Day2.parse(&lines).unwrap();
+
+    assert_eq!(passwords.len(), 2);
+    assert_eq!(Day2.part_one(&passwords), Some(2));
+    assert_eq!(Day2.part_two(&passwords), Some(1));
+  }
+
+  #[test]
+  fn it_reports_out_of_range_positions() {
+    let short = Password::from_str("2-9 c: ccc").unwrap();
+    assert_eq!(
+      short.check_positions(Unit::Chars).unwrap_err().to_string(),
+      "position 9 is past the end of the 3 unit password"
+    );
+    assert!(!short.is_valid_new_rule());
+    assert!(short.is_valid_old_rule());
+
+    let zero = Password::from_str("0-1 c: ccc").unwrap();
+    assert_eq!(
+      zero.check_positions(Unit::Chars).unwrap_err().to_string(),
+      "position 0 is out of range, positions start at 1"
+    );
+    assert!(!zero.is_valid_new_rule());
+    assert!(Password::from_str("1-3 a: abc")
+      .unwrap()
+      .check_positions(Unit::Chars)
+      .is_ok());
+  }
+
+  #[test]
+  fn it_splits_passwords_into_units() {
+    // "e" followed by a combining acute accent is two chars but a single grapheme
+    let accented = "cafe\u{301}s";
+    assert_eq!(Unit::Chars.split(accented).len(), 6);
+    assert_eq!(Unit::Graphemes.split(accented).len(), 5);
+    assert_eq!(Unit::Graphemes.split(accented)[3], "e\u{301}");
+  }
+
+  #[test]
+  fn it_finds_overlapping_occurrences() {
+    let units = Unit::Chars.split("abababa");
+    assert_eq!(
+      occurrences(&units, &Unit::Chars.split("aba")),
+      vec![0, 2, 4]
+    );
+    assert_eq!(occurrences(&units, &Unit::Chars.split("b")), vec![1, 3, 5]);
+    assert_eq!(occurrences(&units, &[]), Vec::<usize>::new());
+    assert_eq!(
+      occurrences(&units[..1], &Unit::Chars.split("ab")),
+      Vec::<usize>::new()
+    );
+  }
+
+  #[test]
+  fn it_checks_password_validity_old_rule() {
+    assert_eq!(
+      Password::from_str("1-3 a: abcde")
+        .unwrap()
+        .is_valid_old_rule(),
+      true
+    );
+    assert_eq!(
+      Password::from_str("1-3 b: cdefg")
+        .unwrap()
+        .is_valid_old_rule(),
+      false
+    );
+    assert_eq!(
+      Password::from_str("2-9 c: ccccccccc")
+        .unwrap()
+        .is_valid_old_rule(),
+      true
+    );
+  }
+
+  #[test]
+  fn it_checks_password_validity_new_rule() {
+    assert_eq!(
+      Password::from_str("1-3 a: abcde")
+        .unwrap()
+        .is_valid_new_rule(),
+      true
+    );
+    assert_eq!(
+      Password::from_str("1-3 b: cdefg")
+        .unwrap()
+        .is_valid_new_rule(),
+      false
+    );
+    assert_eq!(
+      Password::from_str("2-9 c: ccccccccc")
+        .unwrap()
+        .is_valid_new_rule(),
+      false
+    );
+  }
+}
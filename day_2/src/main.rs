@@ -1,134 +1,168 @@
-use std::fs::File;
-use std::io::prelude::*;
-
-#[derive(Debug)]
-struct Password {
-  start: usize,
-  end: usize,
-  requirement: String,
-  password: String,
-}
+use std::env;
+use std::process;
 
-impl Password {
-  pub fn parse_password(line: &str) -> Password {
-    let results = line.split(" ").collect::<Vec<&str>>();
-    assert!(results.len() == 3, "could not parse password line");
-    let requirements = results[0]
-      .split("-")
-      .map(|r| r.trim())
-      .collect::<Vec<&str>>();
-    assert!(requirements.len() == 2, "invalid password requirements");
-    let (start, end) = (
-      requirements[0].parse().unwrap(),
-      requirements[1].parse().unwrap(),
-    );
+use day_2::{policy, report};
+use day_2::{Day2, Password, Unit};
+use shared::{InputSource, Part, Puzzle};
 
-    let requirement = results[1].replace(":", "").trim().to_string();
+const USAGE: &str =
+  "Usage: day_2 [INPUT] [--policies FILE] [--report csv|json] [--unit chars|graphemes]";
 
-    let password = results[2].trim().to_string();
+#[derive(Debug, PartialEq)]
+enum ReportFormat {
+  Csv,
+  Json,
+}
 
-    Password {
-      start,
-      end,
-      requirement,
-      password,
-    }
-  }
+#[derive(Debug, Default, PartialEq)]
+struct Options {
+  input: Option<String>,
+  policies: Option<String>,
+  report: Option<ReportFormat>,
+  unit: Unit,
+}
 
-  pub fn is_valid_old_rule(&self) -> bool {
-    let occurances = self.password.split("").into_iter().fold(0, |count, chr| {
-      if chr == self.requirement {
-        return count + 1;
+fn parse_args(args: &[String]) -> Result<Options, String> {
+  let mut options = Options::default();
+  let mut unit = None;
+
+  options.input = shared::parse_input_and_flags(args, |flag, args| {
+    match flag {
+      "--policies" => options.policies = Some(args.value(flag, "a path")?.to_string()),
+      "--report" => {
+        let value = args.value(flag, "a format")?;
+        options.report = match value {
+          "csv" => Some(ReportFormat::Csv),
+          "json" => Some(ReportFormat::Json),
+          _ => {
+            return Err(format!(
+              "invalid report format {:?}, expected csv or json",
+              value
+            ))
+          }
+        };
+      }
+      "--unit" => {
+        let value = args.value(flag, "a value")?;
+        unit = match value {
+          "chars" => Some(Unit::Chars),
+          "graphemes" => Some(Unit::Graphemes),
+          _ => {
+            return Err(format!(
+              "invalid unit {:?}, expected chars or graphemes",
+              value
+            ))
+          }
+        };
       }
+      _ => return Ok(false),
+    }
 
-      count
-    });
+    Ok(true)
+  })?;
 
-    if occurances >= self.start && occurances <= self.end {
-      return true;
+  if let Some(unit) = unit {
+    if options.policies.is_none() && options.report.is_none() {
+      return Err("--unit can only be used with --policies or --report".to_string());
     }
+    options.unit = unit;
+  }
 
-    false
+  Ok(options)
+}
+
+fn main() {
+  let args = env::args().skip(1).collect::<Vec<String>>();
+  let options = parse_args(&args).unwrap_or_else(|err| {
+    eprintln!("Error: {}\n\n{}", err, USAGE);
+    process::exit(2);
+  });
+  let input = InputSource::resolve(options.input.as_deref(), Day2.input_dir());
+
+  let lines = shared::unwrap_or_exit(input.read());
+  let list = day_2::parse_password_lines(&lines);
+  for rejected in list.rejected.iter() {
+    eprintln!("Skipping {}", rejected);
+  }
+  for (line, password) in list.passwords.iter() {
+    if let Err(err) = password.check_positions(options.unit) {
+      eprintln!(
+        "Warning: line {}: {}, positional policies reject it",
+        line, err
+      );
+    }
   }
 
-  pub fn is_valid_new_rule(&self) -> bool {
-    let password_chars = self
-      .password
-      .chars()
-      .map(|c| c.to_string())
-      .collect::<Vec<String>>();
-    let is_char_idx_1 = password_chars[self.start - 1] == self.requirement;
-    let is_char_idx_2 = password_chars[self.end - 1] == self.requirement;
+  match (options.policies, options.report) {
+    (None, None) => {
+      let valid_lines = list
+        .passwords
+        .iter()
+        .map(|(line, _)| lines[line - 1].to_string())
+        .collect::<Vec<String>>();
+      let results = shared::unwrap_or_exit(Day2.solve(&valid_lines, &Part::ALL));
 
-    (is_char_idx_1 && !is_char_idx_2) || (!is_char_idx_1 && is_char_idx_2)
+      shared::print_results(2, &results);
+    }
+    (policies, Some(format)) => {
+      let policies = match policies {
+        Some(policies) => shared::unwrap_or_exit(policy::read_policies(&policies)),
+        None => policy::puzzle_policies(),
+      };
+      let reports = report::build(&list, &policies, options.unit);
+
+      match format {
+        ReportFormat::Csv => print!("{}", report::to_csv(&reports, &policies)),
+        ReportFormat::Json => match report::to_json(&reports) {
+          Ok(json) => println!("{}", json),
+          Err(err) => eprintln!("Error: {}", err),
+        },
+      }
+    }
+    (Some(policies), None) => {
+      let policies = shared::unwrap_or_exit(policy::read_policies(&policies));
+      let passwords = list
+        .passwords
+        .into_iter()
+        .map(|(_, password)| password)
+        .collect::<Vec<Password>>();
+
+      for named in policies.iter() {
+        let valid = policy::count_valid(&passwords, named.policy.as_ref(), options.unit);
+        println!("{}: {} of {} valid", named.name, valid, passwords.len());
+      }
+    }
   }
-}
 
-fn main() {
-  let mut file = File::open("input.txt").unwrap();
-  let mut contents = String::new();
-
-  file.read_to_string(&mut contents).unwrap();
-  let lines = contents.lines().collect::<Vec<&str>>();
-  let num_valid_old_rule = lines
-    .clone()
-    .into_iter()
-    .filter(|line| Password::parse_password(line).is_valid_old_rule())
-    .count();
-
-  let num_valid_new_rule = lines
-    .clone()
-    .into_iter()
-    .filter(|line| Password::parse_password(line).is_valid_new_rule())
-    .count();
-
-  println!("# valid passwords {} with the old rule", num_valid_old_rule);
-  println!("# valid passwords {} with the new rule", num_valid_new_rule);
+  if !list.rejected.is_empty() {
+    process::exit(1);
+  }
 }
 
 #[cfg(test)]
 mod tests {
   use super::*;
+  use shared::split_args as args;
 
   #[test]
-  fn it_parses_password_list() {
-    let line = "1-3 a: abcde";
-    let password = Password::parse_password(line);
-    assert_eq!(password.start, 1);
-    assert_eq!(password.end, 3);
-    assert_eq!(password.requirement, "a");
-    assert_eq!(password.password, "abcde");
-  }
+  fn it_parses_the_policies_flag() {
+    assert_eq!(parse_args(&args("")).unwrap(), Options::default());
 
-  #[test]
-  fn it_checks_password_validity_old_rule() {
-    assert_eq!(
-      Password::parse_password("1-3 a: abcde").is_valid_old_rule(),
-      true
-    );
-    assert_eq!(
-      Password::parse_password("1-3 b: cdefg").is_valid_old_rule(),
-      false
-    );
-    assert_eq!(
-      Password::parse_password("2-9 c: ccccccccc").is_valid_old_rule(),
-      true
-    );
-  }
+    let options = parse_args(&args("in.txt --policies p.txt --unit graphemes")).unwrap();
+    assert_eq!(options.input, Some("in.txt".to_string()));
+    assert_eq!(options.policies, Some("p.txt".to_string()));
+    assert_eq!(options.unit, Unit::Graphemes);
 
-  #[test]
-  fn it_checks_password_validity_new_rule() {
-    assert_eq!(
-      Password::parse_password("1-3 a: abcde").is_valid_new_rule(),
-      true
-    );
-    assert_eq!(
-      Password::parse_password("1-3 b: cdefg").is_valid_new_rule(),
-      false
-    );
+    assert!(parse_args(&args("--policies")).is_err());
+    assert!(parse_args(&args("--unit graphemes")).is_err());
     assert_eq!(
-      Password::parse_password("2-9 c: ccccccccc").is_valid_new_rule(),
-      false
+      parse_args(&args("--report csv --unit graphemes"))
+        .unwrap()
+        .report,
+      Some(ReportFormat::Csv)
     );
+    assert!(parse_args(&args("--report xml")).is_err());
+    assert!(parse_args(&args("--policies p.txt --unit bytes")).is_err());
+    assert!(parse_args(&args("a.txt b.txt")).is_err());
   }
 }
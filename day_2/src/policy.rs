@@ -0,0 +1,376 @@
+use regex::Regex;
+use shared::InputError;
+
+use crate::{occurrences, Password, Unit};
+
+/// A rule a password has to satisfy, measuring positions and counts in `unit`. `check`
+/// explains why a password fails.
+pub trait Policy {
+  fn check(&self, password: &Password, unit: Unit) -> Result<(), String>;
+
+  fn is_valid(&self, password: &Password, unit: Unit) -> bool {
+    self.check(password, unit).is_ok()
+  }
+}
+
+/// The requirement occurs between the line's two numbers of times, inclusive. Requirements of
+/// several units may overlap, "aba" occurs twice in "ababa".
+pub struct RangeCount;
+
+/// The requirement starts at exactly one of the line's two (1-based) positions.
+pub struct XorPositions;
+
+/// The requirement starts at `k` or more of the given (1-based) positions.
+pub struct AtLeast {
+  pub k: usize,
+  pub positions: Vec<usize>,
+}
+
+/// No unit of the password is the character.
+pub struct Forbidden(pub char);
+
+/// The password matches the regular expression.
+pub struct Pattern(pub Regex);
+
+/// Whether the requirement starts at the 1-based `position`.
+fn requirement_at(units: &[&str], requirement: &[&str], position: usize) -> bool {
+  position > 0 && position <= units.len() && units[position - 1..].starts_with(requirement)
+}
+
+impl Policy for RangeCount {
+  fn check(&self, password: &Password, unit: Unit) -> Result<(), String> {
+    let units = unit.split(&password.password);
+    let count = occurrences(&units, &unit.split(&password.requirement)).len();
+
+    if (password.start..=password.end).contains(&count) {
+      Ok(())
+    } else {
+      Err(format!(
+        "count {} not in {}..{}",
+        count, password.start, password.end
+      ))
+    }
+  }
+}
+
+impl Policy for XorPositions {
+  fn check(&self, password: &Password, unit: Unit) -> Result<(), String> {
+    password
+      .check_positions(unit)
+      .map_err(|err| err.to_string())?;
+
+    let units = unit.split(&password.password);
+    let requirement = unit.split(&password.requirement);
+    match (
+      requirement_at(&units, &requirement, password.start),
+      requirement_at(&units, &requirement, password.end),
+    ) {
+      (true, false) | (false, true) => Ok(()),
+      (true, true) => Err("both positions match".to_string()),
+      (false, false) => Err("neither position matches".to_string()),
+    }
+  }
+}
+
+impl Policy for AtLeast {
+  fn check(&self, password: &Password, unit: Unit) -> Result<(), String> {
+    let units = unit.split(&password.password);
+    let requirement = unit.split(&password.requirement);
+    let matches = self
+      .positions
+      .iter()
+      .filter(|position| requirement_at(&units, &requirement, **position))
+      .count();
+
+    if matches >= self.k {
+      Ok(())
+    } else {
+      Err(format!(
+        "{} of {} positions match, need {}",
+        matches,
+        self.positions.len(),
+        self.k
+      ))
+    }
+  }
+}
+
+impl Policy for Forbidden {
+  fn check(&self, password: &Password, unit: Unit) -> Result<(), String> {
+    let forbidden = self.0.to_string();
+
+    match unit
+      .split(&password.password)
+      .iter()
+      .position(|u| *u == forbidden)
+    {
+      Some(i) => Err(format!("contains {:?} at position {}", self.0, i + 1)),
+      None => Ok(()),
+    }
+  }
+}
+
+impl Policy for Pattern {
+  fn check(&self, password: &Password, _unit: Unit) -> Result<(), String> {
+    if self.0.is_match(&password.password) {
+      Ok(())
+    } else {
+      Err(format!("does not match /{}/", self.0))
+    }
+  }
+}
+
+/// A policy declared in a policy file, one per line:
+///
+/// ```text
+/// # name: kind [arguments]
+/// sled: range-count
+/// toboggan: xor-positions
+/// two-of-three: at-least 2 of 1,2,3
+/// no-z: forbid z
+/// lowercase: regex ^[a-z]+$
+/// ```
+pub struct NamedPolicy {
+  pub name: String,
+  pub policy: Box<dyn Policy>,
+}
+
+impl NamedPolicy {
+  pub fn parse(line: &str) -> Result<NamedPolicy, String> {
+    let (name, definition) = match line.find(':') {
+      Some(i) => (line[..i].trim(), line[i + 1..].trim()),
+      None => return Err("expected \"name: kind [arguments]\"".to_string()),
+    };
+    if name.is_empty() {
+      return Err("missing policy name".to_string());
+    }
+
+    let (kind, arguments) = match definition.find(' ') {
+      Some(i) => (&definition[..i], definition[i + 1..].trim()),
+      None => (definition, ""),
+    };
+
+    let policy: Box<dyn Policy> = match (kind, arguments) {
+      ("range-count", "") => Box::new(RangeCount),
+      ("xor-positions", "") => Box::new(XorPositions),
+      ("at-least", arguments) => Box::new(parse_at_least(arguments)?),
+      ("forbid", arguments) => {
+        let mut chars = arguments.chars();
+        match (chars.next(), chars.next()) {
+          (Some(c), None) => Box::new(Forbidden(c)),
+          _ => {
+            return Err(format!(
+              "forbid takes a single character, got {:?}",
+              arguments
+            ))
+          }
+        }
+      }
+      ("regex", "") => return Err("regex requires a pattern".to_string()),
+      ("regex", pattern) => Box::new(Pattern(Regex::new(pattern).map_err(|err| err.to_string())?)),
+      ("range-count", _) | ("xor-positions", _) => {
+        return Err(format!("{} takes no arguments", kind))
+      }
+      (kind, _) => return Err(format!("unknown policy kind {:?}", kind)),
+    };
+
+    Ok(NamedPolicy {
+      name: name.to_string(),
+      policy,
+    })
+  }
+}
+
+/// Parses `K of P1,P2,...`.
+fn parse_at_least(arguments: &str) -> Result<AtLeast, String> {
+  let tokens = arguments.split_whitespace().collect::<Vec<&str>>();
+  let (k, positions) = match tokens.as_slice() {
+    [k, "of", positions] => (k, positions),
+    _ => return Err("expected \"at-least K of P1,P2,...\"".to_string()),
+  };
+
+  let k = k
+    .parse::<usize>()
+    .map_err(|_| format!("invalid count {:?}", k))?;
+  let positions = positions
+    .split(',')
+    .map(|position| {
+      position
+        .trim()
+        .parse::<usize>()
+        .ok()
+        .filter(|p| *p > 0)
+        .ok_or(format!("invalid position {:?}", position))
+    })
+    .collect::<Result<Vec<usize>, String>>()?;
+
+  Ok(AtLeast { k, positions })
+}
+
+/// Parses a policy file, skipping blank lines and `#` comments.
+pub fn parse_policies(lines: &[String]) -> Result<Vec<NamedPolicy>, InputError> {
+  lines
+    .iter()
+    .enumerate()
+    .filter(|(_, line)| !line.trim().is_empty() && !line.trim_start().starts_with('#'))
+    .map(|(i, line)| NamedPolicy::parse(line).map_err(|err| InputError::parse(i + 1, line, err)))
+    .collect()
+}
+
+pub fn read_policies(filename: &str) -> Result<Vec<NamedPolicy>, InputError> {
+  parse_policies(&shared::try_read_file(filename)?)
+}
+
+/// The two policies from the puzzle, as declared in `policies.txt`.
+pub fn puzzle_policies() -> Vec<NamedPolicy> {
+  let lines = include_str!("../policies.txt")
+    .lines()
+    .map(|line| line.to_string())
+    .collect::<Vec<String>>();
+
+  parse_policies(&lines).expect("the puzzle policies are valid")
+}
+
+pub fn count_valid(passwords: &[Password], policy: &dyn Policy, unit: Unit) -> usize {
+  passwords
+    .iter()
+    .filter(|password| policy.is_valid(password, unit))
+    .count()
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn password(line: &str) -> Password {
+    line.parse().unwrap()
+  }
+
+  fn policy(line: &str) -> Box<dyn Policy> {
+    NamedPolicy::parse(line).unwrap().policy
+  }
+
+  #[test]
+  fn it_explains_failures() {
+    assert_eq!(
+      RangeCount.check(&password("1-3 b: bbbbb"), Unit::Chars),
+      Err("count 5 not in 1..3".to_string())
+    );
+    assert_eq!(
+      XorPositions.check(&password("2-9 c: ccccccccc"), Unit::Chars),
+      Err("both positions match".to_string())
+    );
+    assert_eq!(
+      XorPositions.check(&password("1-3 b: cdefg"), Unit::Chars),
+      Err("neither position matches".to_string())
+    );
+    assert!(XorPositions.is_valid(&password("1-3 a: abcde"), Unit::Chars));
+  }
+
+  #[test]
+  fn it_parses_policy_declarations() {
+    let lines = vec![
+      "# comment".to_string(),
+      "sled: range-count".to_string(),
+      "".to_string(),
+      "two-of-three: at-least 2 of 1,2,3".to_string(),
+      "no-z: forbid z".to_string(),
+      "lowercase: regex ^[a-z]+$".to_string(),
+    ];
+    let policies = parse_policies(&lines).unwrap();
+    let names = policies
+      .iter()
+      .map(|p| p.name.as_str())
+      .collect::<Vec<&str>>();
+    assert_eq!(names, vec!["sled", "two-of-three", "no-z", "lowercase"]);
+
+    let passwords = vec![
+      password("1-3 a: aab"),
+      password("1-3 a: bza"),
+      password("1-3 a: ABC"),
+    ];
+    let counts = policies
+      .iter()
+      .map(|p| count_valid(&passwords, p.policy.as_ref(), Unit::Chars))
+      .collect::<Vec<usize>>();
+    assert_eq!(counts, vec![2, 1, 2, 2]);
+
+    let names = puzzle_policies()
+      .into_iter()
+      .map(|p| p.name)
+      .collect::<Vec<String>>();
+    assert_eq!(names, vec!["sled", "toboggan"]);
+  }
+
+  #[test]
+  fn it_applies_positional_policies() {
+    let at_least = policy("p: at-least 2 of 1,3,20");
+    assert!(at_least.is_valid(&password("1-3 a: aba"), Unit::Chars));
+    assert_eq!(
+      at_least.check(&password("1-3 a: abb"), Unit::Chars),
+      Err("1 of 3 positions match, need 2".to_string())
+    );
+    assert_eq!(
+      policy("p: forbid x").check(&password("1-3 a: aax"), Unit::Chars),
+      Err("contains 'x' at position 3".to_string())
+    );
+  }
+
+  #[test]
+  fn it_matches_multi_unit_requirements() {
+    assert!(RangeCount.is_valid(&password("1-3 ab: xabab"), Unit::Chars));
+    assert_eq!(
+      RangeCount.check(&password("1-2 aa: aaaa"), Unit::Chars),
+      Err("count 3 not in 1..2".to_string())
+    );
+    assert!(XorPositions.is_valid(&password("1-3 ab: abbb"), Unit::Chars));
+    assert_eq!(
+      XorPositions.check(&password("1-3 ab: abab"), Unit::Chars),
+      Err("both positions match".to_string())
+    );
+    assert!(!XorPositions.is_valid(&password("3-4 ab: abba"), Unit::Chars));
+  }
+
+  #[test]
+  fn it_measures_in_the_chosen_unit() {
+    // The password is "é" written as "e" plus a combining accent, followed by "xe"
+    let accented = password("1-1 e: e\u{301}xe");
+    assert!(!RangeCount.is_valid(&accented, Unit::Chars));
+    assert!(RangeCount.is_valid(&accented, Unit::Graphemes));
+
+    let positions = password("1-2 x: e\u{301}xe");
+    assert!(!XorPositions.is_valid(&positions, Unit::Chars));
+    assert!(XorPositions.is_valid(&positions, Unit::Graphemes));
+
+    let grapheme = password("1-3 e\u{301}: e\u{301}xe");
+    assert!(RangeCount.is_valid(&grapheme, Unit::Graphemes));
+    assert!(XorPositions.is_valid(&grapheme, Unit::Graphemes));
+    assert_eq!(
+      Forbidden('e').check(&grapheme, Unit::Graphemes),
+      Err("contains 'e' at position 3".to_string())
+    );
+    assert_eq!(
+      Forbidden('e').check(&grapheme, Unit::Chars),
+      Err("contains 'e' at position 1".to_string())
+    );
+  }
+
+  #[test]
+  fn it_rejects_invalid_policies() {
+    assert!(NamedPolicy::parse("range-count").is_err());
+    assert!(NamedPolicy::parse(": range-count").is_err());
+    assert!(NamedPolicy::parse("p: range-count 3").is_err());
+    assert!(NamedPolicy::parse("p: at-least 2 of 0,1").is_err());
+    assert!(NamedPolicy::parse("p: at-least two of 1,2").is_err());
+    assert!(NamedPolicy::parse("p: forbid xy").is_err());
+    assert!(NamedPolicy::parse("p: regex (").is_err());
+    assert!(NamedPolicy::parse("p: regex").is_err());
+    assert!(NamedPolicy::parse("p: sparkle").is_err());
+
+    let lines = vec!["ok: range-count".to_string(), "bad".to_string()];
+    match parse_policies(&lines) {
+      Err(InputError::Parse { line, .. }) => assert_eq!(line, 2),
+      _ => panic!("expected a parse error"),
+    }
+  }
+}
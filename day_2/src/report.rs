@@ -0,0 +1,224 @@
+use serde::Serialize;
+
+use crate::policy::NamedPolicy;
+use crate::{PasswordList, Unit};
+
+/// The outcome of one policy for one password.
+#[derive(Debug, PartialEq, Serialize)]
+pub struct PolicyOutcome {
+  pub policy: String,
+  pub valid: bool,
+  pub reason: Option<String>,
+}
+
+/// One input line, either parsed with every policy's outcome or rejected with its error.
+#[derive(Debug, PartialEq, Serialize)]
+pub struct LineReport {
+  pub line: usize,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub start: Option<usize>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub end: Option<usize>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub requirement: Option<String>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub password: Option<String>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub error: Option<String>,
+  pub policies: Vec<PolicyOutcome>,
+}
+
+/// Checks every parsed password against every policy, keeping rejected lines in line order so
+/// that reports for different policy files line up.
+pub fn build(list: &PasswordList, policies: &[NamedPolicy], unit: Unit) -> Vec<LineReport> {
+  let mut reports = list
+    .passwords
+    .iter()
+    .map(|(line, password)| LineReport {
+      line: *line,
+      start: Some(password.start),
+      end: Some(password.end),
+      requirement: Some(password.requirement.to_string()),
+      password: Some(password.password.to_string()),
+      error: None,
+      policies: policies
+        .iter()
+        .map(|named| {
+          let reason = named.policy.check(password, unit).err();
+          PolicyOutcome {
+            policy: named.name.to_string(),
+            valid: reason.is_none(),
+            reason,
+          }
+        })
+        .collect(),
+    })
+    .chain(list.rejected.iter().map(|rejected| LineReport {
+      line: rejected.line,
+      start: None,
+      end: None,
+      requirement: None,
+      password: None,
+      error: Some(rejected.error.to_string()),
+      policies: vec![],
+    }))
+    .collect::<Vec<LineReport>>();
+
+  reports.sort_by_key(|report| report.line);
+  reports
+}
+
+pub fn to_json(reports: &[LineReport]) -> Result<String, serde_json::Error> {
+  serde_json::to_string_pretty(reports)
+}
+
+/// Quotes a field if it contains a separator, quote or line break.
+fn csv_field(field: &str) -> String {
+  if field.contains(&[',', '"', '\n', '\r'][..]) {
+    format!("\"{}\"", field.replace('"', "\"\""))
+  } else {
+    field.to_string()
+  }
+}
+
+/// One row per line with a `<policy>` pass/fail column and a `<policy> reason` column for each
+/// policy. Rejected lines leave the policy columns empty.
+pub fn to_csv(reports: &[LineReport], policies: &[NamedPolicy]) -> String {
+  let mut header = ["line", "start", "end", "requirement", "password", "error"]
+    .iter()
+    .map(|column| column.to_string())
+    .collect::<Vec<String>>();
+  for named in policies.iter() {
+    header.push(named.name.to_string());
+    header.push(format!("{} reason", named.name));
+  }
+
+  let mut rows = vec![header];
+  for report in reports.iter() {
+    let mut row = vec![
+      report.line.to_string(),
+      report.start.map_or(String::new(), |s| s.to_string()),
+      report.end.map_or(String::new(), |e| e.to_string()),
+      report.requirement.clone().unwrap_or_default(),
+      report.password.clone().unwrap_or_default(),
+      report.error.clone().unwrap_or_default(),
+    ];
+    if report.policies.is_empty() {
+      row.resize(row.len() + 2 * policies.len(), String::new());
+    }
+    for outcome in report.policies.iter() {
+      row.push(if outcome.valid { "pass" } else { "fail" }.to_string());
+      row.push(outcome.reason.clone().unwrap_or_default());
+    }
+    rows.push(row);
+  }
+
+  rows
+    .iter()
+    .map(|row| {
+      row
+        .iter()
+        .map(|field| csv_field(field))
+        .collect::<Vec<String>>()
+        .join(",")
+    })
+    .map(|row| row + "\n")
+    .collect()
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::parse_password_lines;
+  use crate::policy::parse_policies;
+
+  fn fixture() -> (PasswordList, Vec<NamedPolicy>) {
+    let lines = vec![
+      "1-3 a: abcde".to_string(),
+      "1-3 b: cdefg".to_string(),
+      "1 a: abcde".to_string(),
+      "2-9 c: ccccccccc".to_string(),
+    ];
+    let policies = vec![
+      "sled: range-count".to_string(),
+      "toboggan: xor-positions".to_string(),
+    ];
+
+    (
+      parse_password_lines(&lines),
+      parse_policies(&policies).unwrap(),
+    )
+  }
+
+  #[test]
+  fn it_reports_every_line_in_order() {
+    let (list, policies) = fixture();
+    let reports = build(&list, &policies, Unit::Chars);
+
+    assert_eq!(
+      reports.iter().map(|r| r.line).collect::<Vec<usize>>(),
+      vec![1, 2, 3, 4]
+    );
+    assert_eq!(
+      reports[3].policies,
+      vec![
+        PolicyOutcome {
+          policy: "sled".to_string(),
+          valid: true,
+          reason: None,
+        },
+        PolicyOutcome {
+          policy: "toboggan".to_string(),
+          valid: false,
+          reason: Some("both positions match".to_string()),
+        },
+      ]
+    );
+    assert!(reports[2].policies.is_empty());
+    assert!(reports[2].error.is_some());
+  }
+
+  #[test]
+  fn it_writes_csv() {
+    let (list, policies) = fixture();
+    let csv = to_csv(&build(&list, &policies, Unit::Chars), &policies);
+
+    assert_eq!(
+      csv,
+      "line,start,end,requirement,password,error,sled,sled reason,toboggan,toboggan reason\n\
+       1,1,3,a,abcde,,pass,,pass,\n\
+       2,1,3,b,cdefg,,fail,count 0 not in 1..3,fail,neither position matches\n\
+       3,,,,,\"expected \"\"<start>-<end> <requirement>: <password>\"\"\",,,,\n\
+       4,2,9,c,ccccccccc,,pass,,fail,both positions match\n"
+    );
+  }
+
+  #[test]
+  fn it_writes_json() {
+    let (list, policies) = fixture();
+    let reports = build(&list, &policies, Unit::Chars);
+    let json = serde_json::to_value(&reports[1..3]).unwrap();
+
+    assert_eq!(
+      json,
+      serde_json::json!([
+        {
+          "line": 2,
+          "start": 1,
+          "end": 3,
+          "requirement": "b",
+          "password": "cdefg",
+          "policies": [
+            { "policy": "sled", "valid": false, "reason": "count 0 not in 1..3" },
+            { "policy": "toboggan", "valid": false, "reason": "neither position matches" }
+          ]
+        },
+        {
+          "line": 3,
+          "error": "expected \"<start>-<end> <requirement>: <password>\"",
+          "policies": []
+        }
+      ])
+    );
+  }
+}
@@ -0,0 +1,432 @@
+use std::convert::TryFrom;
+use std::error::Error;
+use std::fmt;
+use std::ops::RangeInclusive;
+use std::str::FromStr;
+
+use rayon::prelude::*;
+
+use shared::{Cell, Grid, InputError, Solver, UnknownCell, Wrapping};
+
+pub mod render;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Terrain {
+  Open,
+  Tree,
+}
+
+impl TryFrom<char> for Terrain {
+  type Error = UnknownCell;
+
+  fn try_from(c: char) -> Result<Terrain, UnknownCell> {
+    match c {
+      '.' => Ok(Terrain::Open),
+      '#' => Ok(Terrain::Tree),
+      other => Err(UnknownCell(other)),
+    }
+  }
+}
+
+impl Cell for Terrain {
+  fn to_char(self) -> char {
+    match self {
+      Terrain::Open => '.',
+      Terrain::Tree => '#',
+    }
+  }
+}
+
+impl fmt::Display for Terrain {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    write!(f, "{}", self.to_char())
+  }
+}
+
+pub fn parse_input_file(filename: &str) -> Result<Grid<Terrain>, InputError> {
+  let lines = shared::try_read_file(filename)?;
+
+  parse_map(&lines)
+}
+
+/// The map repeats to the right, so it wraps horizontally.
+pub fn parse_map(lines: &[String]) -> Result<Grid<Terrain>, InputError> {
+  let map = Grid::parse_cells(lines)?;
+
+  Ok(map.with_wrapping(Wrapping::Horizontal))
+}
+
+/// How far the sled moves per step: `right` columns (leftwards when negative) for every `down`
+/// rows, written `right/down`. The step is taken as given, so `2/2` lands on every other
+/// cell that `1/1` does.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Slope {
+  pub right: isize,
+  pub down: usize,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InvalidSlope(pub String);
+
+impl fmt::Display for InvalidSlope {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    write!(
+      f,
+      "invalid slope {:?}, expected RIGHT/DOWN with DOWN > 0",
+      self.0
+    )
+  }
+}
+
+impl Error for InvalidSlope {}
+
+impl Slope {
+  /// A slope that never moves down would never reach the bottom, so `down` must be positive.
+  pub fn new(right: isize, down: usize) -> Option<Slope> {
+    if down > 0 {
+      Some(Slope { right, down })
+    } else {
+      None
+    }
+  }
+}
+
+impl FromStr for Slope {
+  type Err = InvalidSlope;
+
+  fn from_str(slope: &str) -> Result<Slope, InvalidSlope> {
+    let invalid = || InvalidSlope(slope.to_string());
+    let (right, down) = match slope.find('/') {
+      Some(i) => (&slope[..i], &slope[i + 1..]),
+      None => return Err(invalid()),
+    };
+    let right = right.trim().parse::<isize>().map_err(|_| invalid())?;
+    let down = down.trim().parse::<usize>().map_err(|_| invalid())?;
+
+    Slope::new(right, down).ok_or_else(invalid)
+  }
+}
+
+impl fmt::Display for Slope {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    write!(f, "{}/{}", self.right, self.down)
+  }
+}
+
+/// What happens when the sled runs off the left or right edge of the map.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Boundary {
+  /// The map repeats sideways, as in the puzzle.
+  Wrap,
+  /// The ride ends at the edge.
+  Stop,
+  /// The sled bounces off the edge and carries on in the opposite direction.
+  Reflect,
+}
+
+impl FromStr for Boundary {
+  type Err = String;
+
+  fn from_str(boundary: &str) -> Result<Boundary, String> {
+    match boundary {
+      "wrap" => Ok(Boundary::Wrap),
+      "stop" => Ok(Boundary::Stop),
+      "reflect" => Ok(Boundary::Reflect),
+      other => Err(format!(
+        "invalid boundary {:?}, expected wrap, stop or reflect",
+        other
+      )),
+    }
+  }
+}
+
+impl Boundary {
+  /// The map column for a column that may lie outside a map `width` wide.
+  fn column(self, col: isize, width: usize) -> Option<usize> {
+    let width = width as isize;
+    match self {
+      Boundary::Wrap => Some(col.rem_euclid(width) as usize),
+      Boundary::Stop if (0..width).contains(&col) => Some(col as usize),
+      Boundary::Stop => None,
+      Boundary::Reflect if width == 1 => Some(0),
+      Boundary::Reflect => {
+        let period = 2 * (width - 1);
+        let col = col.rem_euclid(period);
+        Some(if col < width { col } else { period - col } as usize)
+      }
+    }
+  }
+
+  /// A move of `right` columns on a map `width` wide, reduced to one that lands on the same
+  /// columns, so that multiplying it by the number of steps taken cannot overflow.
+  fn shift(self, right: isize, width: usize) -> isize {
+    let width = width as isize;
+    match self {
+      Boundary::Wrap => right.rem_euclid(width),
+      Boundary::Stop => right.max(-width).min(width),
+      Boundary::Reflect if width == 1 => 0,
+      Boundary::Reflect => right.rem_euclid(2 * (width - 1)),
+    }
+  }
+}
+
+/// The cells a ride visited, from the top left corner down, and how many of them were trees.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Ride {
+  pub path: Vec<(usize, usize)>,
+  pub trees: usize,
+}
+
+pub fn ride(map: &Grid<Terrain>, slope: Slope, boundary: Boundary) -> Ride {
+  let mut path = vec![];
+  let mut trees = 0;
+  if map.width() == 0 {
+    return Ride { path, trees };
+  }
+  let right = boundary.shift(slope.right, map.width());
+
+  for step in 0usize.. {
+    let row = match step.checked_mul(slope.down) {
+      Some(row) if row < map.height() => row,
+      _ => break,
+    };
+    let col = match boundary.column(step as isize * right, map.width()) {
+      Some(col) => col,
+      None => break,
+    };
+
+    match map[(row, col)] {
+      Terrain::Tree => trees += 1,
+      Terrain::Open => {}
+    }
+    path.push((row, col));
+  }
+
+  Ride { path, trees }
+}
+
+pub fn count_trees(map: &Grid<Terrain>, right_count: usize, down_count: usize) -> usize {
+  match Slope::new(right_count as isize, down_count) {
+    Some(slope) => ride(map, slope, Boundary::Wrap).trees,
+    None => 0,
+  }
+}
+
+/// Tree counts for every slope in a sweep, from the fewest trees to the most. Slopes with
+/// the same count are ordered by `right` and then `down`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Sweep {
+  pub ranked: Vec<(Slope, usize)>,
+}
+
+impl Sweep {
+  pub fn fewest(&self) -> Option<(Slope, usize)> {
+    self.ranked.first().copied()
+  }
+
+  pub fn most(&self) -> Option<(Slope, usize)> {
+    self.ranked.last().copied()
+  }
+}
+
+/// Rides every slope with `right` and `down` in the given bounds, in parallel. `down` values
+/// of 0 are skipped since those slopes never reach the bottom.
+pub fn sweep(
+  map: &Grid<Terrain>,
+  right: RangeInclusive<isize>,
+  down: RangeInclusive<usize>,
+  boundary: Boundary,
+) -> Sweep {
+  let mut ranked = right
+    .into_par_iter()
+    .flat_map(|right| {
+      down
+        .clone()
+        .into_par_iter()
+        .filter_map(move |down| Slope::new(right, down))
+    })
+    .map(|slope| (slope, ride(map, slope, boundary).trees))
+    .collect::<Vec<(Slope, usize)>>();
+  ranked.sort_by_key(|(slope, trees)| (*trees, slope.right, slope.down));
+
+  Sweep { ranked }
+}
+
+/// The `(right, down)` slopes checked in part two.
+pub const PART_TWO_SLOPES: [(usize, usize); 5] = [(1, 1), (3, 1), (5, 1), (7, 1), (1, 2)];
+
+pub fn trees_for_paths_multiplied(map: &Grid<Terrain>, paths: &[(usize, usize)]) -> usize {
+  let tree_counts = paths.iter().fold(1, |counts, paths| {
+    counts * count_trees(map, paths.0, paths.1)
+  });
+
+  tree_counts
+}
+
+pub struct Day3;
+
+impl Solver for Day3 {
+  type Input = Grid<Terrain>;
+  type Answer = usize;
+
+  fn day(&self) -> u8 {
+    3
+  }
+
+  fn input_dir(&self) -> &'static str {
+    env!("CARGO_MANIFEST_DIR")
+  }
+
+  fn parse(&self, lines: &[String]) -> Result<Grid<Terrain>, InputError> {
+    parse_map(lines)
+  }
+
+  fn part_one(&self, map: &Grid<Terrain>) -> Option<usize> {
+    Some(count_trees(map, 3, 1))
+  }
+
+  fn part_two(&self, map: &Grid<Terrain>) -> Option<usize> {
+    Some(trees_for_paths_multiplied(map, &PART_TWO_SLOPES))
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn it_parses_the_input_correctly() {
+    let grid = parse_input_file("test_input.txt").unwrap();
+    println!("{}", grid);
+    assert_eq!(grid.height(), 11, "correct number of rows");
+    assert_eq!(grid.width(), 66, "correct number of columns");
+  }
+
+  #[test]
+  fn it_rejects_unknown_terrain() {
+    let lines = vec!["..#".to_string(), ".X.".to_string()];
+    let err = parse_map(&lines).unwrap_err();
+    assert_eq!(
+      err.to_string(),
+      "line 2: could not parse \".X.\": unknown cell 'X' at column 2"
+    );
+  }
+
+  #[test]
+  fn it_counts_trees_in_map() {
+    let grid = parse_input_file("test_input.txt").unwrap();
+    assert_eq!(count_trees(&grid, 3, 1), 7, "correct number of trees")
+  }
+
+  #[test]
+  fn it_multiplies_trees_in_path() {
+    let map = parse_input_file("test_input.txt").unwrap();
+    let paths = vec![(1, 1), (3, 1), (5, 1), (7, 1), (1, 2)];
+
+    assert_eq!(
+      trees_for_paths_multiplied(&map, &paths),
+      336,
+      "correct multiplied tree count"
+    );
+  }
+
+  #[test]
+  fn it_parses_slopes() {
+    assert_eq!("3/1".parse(), Ok(Slope { right: 3, down: 1 }));
+    assert_eq!("-1/2".parse(), Ok(Slope { right: -1, down: 2 }));
+    assert_eq!(Slope { right: -1, down: 2 }.to_string(), "-1/2");
+    assert!("3".parse::<Slope>().is_err());
+    assert!("3/0".parse::<Slope>().is_err());
+    assert!("3/-1".parse::<Slope>().is_err());
+    assert_eq!(
+      "x/1".parse::<Slope>().unwrap_err().to_string(),
+      "invalid slope \"x/1\", expected RIGHT/DOWN with DOWN > 0"
+    );
+  }
+
+  #[test]
+  fn it_rides_with_each_boundary() {
+    let lines = vec![
+      "..#".to_string(),
+      "#..".to_string(),
+      ".#.".to_string(),
+      "..#".to_string(),
+      "#..".to_string(),
+    ];
+    let map = parse_map(&lines).unwrap();
+    let slope = Slope::new(1, 1).unwrap();
+
+    let wrapped = ride(&map, slope, Boundary::Wrap);
+    assert_eq!(wrapped.path, vec![(0, 0), (1, 1), (2, 2), (3, 0), (4, 1)]);
+    assert_eq!(wrapped.trees, 0);
+
+    let stopped = ride(&map, slope, Boundary::Stop);
+    assert_eq!(stopped.path, vec![(0, 0), (1, 1), (2, 2)]);
+
+    let reflected = ride(&map, slope, Boundary::Reflect);
+    assert_eq!(reflected.path, vec![(0, 0), (1, 1), (2, 2), (3, 1), (4, 0)]);
+    assert_eq!(reflected.trees, 1);
+  }
+
+  #[test]
+  fn it_rides_leftwards() {
+    let map = parse_input_file("test_input.txt").unwrap();
+    let slope = "-3/1".parse().unwrap();
+
+    let wrapped = ride(&map, slope, Boundary::Wrap);
+    assert_eq!(wrapped.path.len(), map.height());
+    assert_eq!(wrapped.path[1], (1, map.width() - 3));
+
+    assert_eq!(ride(&map, slope, Boundary::Stop).path, vec![(0, 0)]);
+    assert_eq!(ride(&map, slope, Boundary::Reflect).path[1], (1, 3));
+
+    let two_down = ride(&map, "1/2".parse().unwrap(), Boundary::Wrap);
+    assert_eq!(two_down.path.len(), 6);
+    assert_eq!(two_down.trees, count_trees(&map, 1, 2));
+  }
+
+  #[test]
+  fn it_rides_slopes_wider_than_the_map() {
+    let map = parse_input_file("test_input.txt").unwrap();
+    let steep = format!("{}/{}", isize::MAX, usize::MAX).parse().unwrap();
+    let wide = format!("{}/1", isize::MAX).parse().unwrap();
+    let same = Slope::new(isize::MAX % map.width() as isize, 1).unwrap();
+
+    assert_eq!(ride(&map, steep, Boundary::Wrap).path, vec![(0, 0)]);
+    assert_eq!(
+      ride(&map, wide, Boundary::Wrap),
+      ride(&map, same, Boundary::Wrap)
+    );
+    assert_eq!(ride(&map, wide, Boundary::Stop).path, vec![(0, 0)]);
+    assert_eq!(ride(&map, wide, Boundary::Reflect).path.len(), map.height());
+  }
+
+  #[test]
+  fn it_ranks_swept_slopes() {
+    let map = parse_input_file("test_input.txt").unwrap();
+    let sweep = sweep(&map, -2..=7, 0..=2, Boundary::Wrap);
+
+    assert_eq!(sweep.ranked.len(), 20);
+    assert!(sweep.ranked.windows(2).all(|w| w[0].1 <= w[1].1));
+    assert!(sweep
+      .ranked
+      .contains(&(Slope { right: 3, down: 1 }, count_trees(&map, 3, 1))));
+
+    let (_, fewest) = sweep.fewest().unwrap();
+    let (_, most) = sweep.most().unwrap();
+    for (right, down) in [(1, 1), (3, 1), (5, 1), (7, 1), (1, 2)].iter() {
+      let trees = count_trees(&map, *right, *down);
+      assert!(fewest <= trees && trees <= most);
+    }
+  }
+
+  #[test]
+  fn it_sweeps_nothing_without_a_downward_slope() {
+    let map = parse_input_file("test_input.txt").unwrap();
+    let sweep = sweep(&map, -1..=1, 0..=0, Boundary::Stop);
+
+    assert!(sweep.ranked.is_empty());
+    assert_eq!(sweep.fewest(), None);
+    assert_eq!(sweep.most(), None);
+  }
+}
@@ -1,92 +1,258 @@
-use std::io::prelude::*;
-use std::{fs::File, println};
+use std::env;
+use std::fs;
+use std::io::{self, Write};
+use std::process;
 
-fn parse_input_file(filename: &str) -> Vec<Vec<String>> {
-  let mut file = File::open(filename).unwrap();
-  let mut contents = String::new();
+use day_3::render;
+use day_3::{Boundary, Day3, Slope};
+use shared::{InputSource, Part, Puzzle};
 
-  file.read_to_string(&mut contents).unwrap();
-  let lines = contents.lines().collect::<Vec<&str>>();
+const USAGE: &str = "Usage: day_3 [INPUT] [--slope RIGHT/DOWN]... [--sweep MAX_RIGHT/MAX_DOWN]
+             [--boundary wrap|stop|reflect]
+             [--render ansi|svg|ppm [--scale N] [--output PATH]]";
 
-  let matrix = lines.iter().fold(vec![], |mut rows, line| {
-    let char_row = line.chars().map(|c| c.to_string()).collect::<Vec<String>>();
+/// Pixels per map cell in SVG images. PPM images default to one pixel per cell, as a map
+/// repeated for the shallowest slopes is already thousands of cells wide.
+const DEFAULT_SCALE: usize = 8;
 
-    rows.push(char_row);
+/// The largest `--sweep` bounds, which already make for two million slopes.
+const MAX_SWEEP: Slope = Slope {
+  right: 1000,
+  down: 1000,
+};
 
-    rows
-  });
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum RenderFormat {
+  Ansi,
+  Svg,
+  Ppm,
+}
 
-  matrix
+#[derive(Debug, PartialEq)]
+struct Options {
+  input: Option<String>,
+  slopes: Vec<Slope>,
+  sweep: Option<Slope>,
+  boundary: Boundary,
+  render: Option<RenderFormat>,
+  scale: usize,
+  output: Option<String>,
 }
 
-fn count_trees(map: &Vec<Vec<String>>, right_count: usize, down_count: usize) -> usize {
-  let max_cols = map[0].len();
-  let max_rows = map.len();
+fn parse_args(args: &[String]) -> Result<Options, String> {
+  let mut slopes = vec![];
+  let mut sweep = None;
+  let mut boundary = None;
+  let mut render = None;
+  let mut scale = None;
+  let mut output = None;
 
-  let mut num_trees = 0;
-  let mut i = 0;
-  let mut j = 0;
-  while i < max_rows {
-    if map[i][j] == "#" {
-      num_trees += 1;
+  let input = shared::parse_input_and_flags(args, |flag, args| {
+    match flag {
+      "--slope" => {
+        let value = args.value(flag, "RIGHT/DOWN")?;
+        slopes.push(value.parse::<Slope>().map_err(|err| err.to_string())?);
+      }
+      "--sweep" => {
+        let value = args.value(flag, "MAX_RIGHT/MAX_DOWN")?;
+        let bounds = value.parse::<Slope>().map_err(|err| err.to_string())?;
+        if bounds.right < 0 {
+          return Err(format!(
+            "--sweep bounds must not be negative, got {}",
+            bounds
+          ));
+        }
+        if bounds.right > MAX_SWEEP.right || bounds.down > MAX_SWEEP.down {
+          return Err(format!(
+            "--sweep bounds must be at most {}, got {}",
+            MAX_SWEEP, bounds
+          ));
+        }
+        sweep = Some(bounds);
+      }
+      "--boundary" => {
+        let value = args.value(flag, "a mode")?;
+        boundary = Some(value.parse::<Boundary>()?);
+      }
+      "--render" => {
+        let value = args.value(flag, "a format")?;
+        render = match value {
+          "ansi" => Some(RenderFormat::Ansi),
+          "svg" => Some(RenderFormat::Svg),
+          "ppm" => Some(RenderFormat::Ppm),
+          _ => {
+            return Err(format!(
+              "invalid render format {:?}, expected ansi, svg or ppm",
+              value
+            ))
+          }
+        };
+      }
+      "--scale" => {
+        let value = args.value(flag, "a number")?;
+        scale = match value.parse::<usize>() {
+          Ok(scale) if scale > 0 => Some(scale),
+          _ => return Err(format!("invalid scale {:?}", value)),
+        };
+      }
+      "--output" => output = Some(args.value(flag, "a path")?.to_string()),
+      _ => return Ok(false),
     }
 
-    j = (j + right_count) % max_cols;
-    i += down_count;
+    Ok(true)
+  })?;
+
+  if boundary.is_some() && slopes.is_empty() && sweep.is_none() && render.is_none() {
+    return Err("--boundary can only be used with --slope, --sweep or --render".to_string());
+  }
+  if (scale.is_some() || output.is_some()) && render.is_none() {
+    return Err("--scale and --output can only be used with --render".to_string());
+  }
+  if render == Some(RenderFormat::Ppm) && output.is_none() {
+    return Err("--render ppm writes a binary image and requires --output".to_string());
   }
 
-  num_trees
+  Ok(Options {
+    input,
+    slopes,
+    sweep,
+    boundary: boundary.unwrap_or(Boundary::Wrap),
+    render,
+    scale: scale.unwrap_or(match render {
+      Some(RenderFormat::Ppm) => 1,
+      _ => DEFAULT_SCALE,
+    }),
+    output,
+  })
 }
 
-fn trees_for_paths_multiplied(map: &Vec<Vec<String>>, paths: &Vec<(usize, usize)>) -> usize {
-  let tree_counts = paths.iter().fold(1, |counts, paths| {
-    counts * count_trees(map, paths.0, paths.1)
+fn main() {
+  let args = env::args().skip(1).collect::<Vec<String>>();
+  let options = parse_args(&args).unwrap_or_else(|err| {
+    eprintln!("Error: {}\n\n{}", err, USAGE);
+    process::exit(2);
   });
+  let input = InputSource::resolve(options.input.as_deref(), Day3.input_dir());
 
-  tree_counts
-}
+  if options.slopes.is_empty() && options.sweep.is_none() && options.render.is_none() {
+    shared::unwrap_or_exit(shared::run(&Day3, &input, &Part::ALL));
+    return;
+  }
 
-fn main() {
-  let map = parse_input_file("input.txt");
-  let num_trees = count_trees(&map, 3, 1);
+  let lines = shared::unwrap_or_exit(input.read());
+  let map = shared::unwrap_or_exit(day_3::parse_map(&lines));
 
-  println!("Num trees {} on single path", num_trees);
+  if let Some(format) = options.render {
+    let slopes = if options.slopes.is_empty() {
+      day_3::PART_TWO_SLOPES
+        .iter()
+        .filter_map(|(right, down)| Slope::new(*right as isize, *down))
+        .collect()
+    } else {
+      options.slopes
+    };
+    let canvas = render::overlay(&map, &slopes, options.boundary);
+    let image = match format {
+      RenderFormat::Ansi => render::to_ansi(&canvas).into_bytes(),
+      RenderFormat::Svg => render::to_svg(&canvas, options.scale).into_bytes(),
+      RenderFormat::Ppm => render::to_ppm(&canvas, options.scale),
+    };
+
+    let written = match &options.output {
+      Some(path) => fs::write(path, &image),
+      None => io::stdout().write_all(&image),
+    };
+    if let Err(err) = written {
+      eprintln!("Error: could not write image: {}", err);
+      process::exit(1);
+    }
+    return;
+  }
+
+  for slope in options.slopes.iter() {
+    let ride = day_3::ride(&map, *slope, options.boundary);
+    println!(
+      "Slope {}: {} trees over {} cells",
+      slope,
+      ride.trees,
+      ride.path.len()
+    );
+  }
 
-  let paths = vec![(1, 1), (3, 1), (5, 1), (7, 1), (1, 2)];
-  let multiplied_count = trees_for_paths_multiplied(&map, &paths);
-  println!(
-    "The product of tree counts for multiple paths is {}",
-    multiplied_count
-  );
+  if let Some(bounds) = options.sweep {
+    let sweep = day_3::sweep(
+      &map,
+      -bounds.right..=bounds.right,
+      1..=bounds.down,
+      options.boundary,
+    );
+    println!("Swept {} slopes", sweep.ranked.len());
+    if let (Some((fewest, fewest_trees)), Some((most, most_trees))) = (sweep.fewest(), sweep.most())
+    {
+      println!("Fewest trees: {} with slope {}", fewest_trees, fewest);
+      println!("Most trees: {} with slope {}", most_trees, most);
+    }
+  }
 }
 
 #[cfg(test)]
 mod tests {
   use super::*;
+  use shared::split_args as args;
 
   #[test]
-  fn it_parses_the_input_correctly() {
-    let grid = parse_input_file("test_input.txt");
-    println!("{:#?}", grid);
-    assert_eq!(grid.len(), 11, "correct number of rows");
-    assert_eq!(grid[0].len(), 66, "correct number of columns");
-  }
+  fn it_parses_slopes_and_boundary() {
+    let options = parse_args(&args("")).unwrap();
+    assert!(options.slopes.is_empty());
+    assert_eq!(options.boundary, Boundary::Wrap);
 
-  #[test]
-  fn it_counts_trees_in_map() {
-    let grid = parse_input_file("test_input.txt");
-    assert_eq!(count_trees(&grid, 3, 1), 7, "correct number of trees")
+    let options = parse_args(&args("map.txt --slope 3/1 --slope -1/2 --boundary reflect")).unwrap();
+    assert_eq!(options.input, Some("map.txt".to_string()));
+    assert_eq!(
+      options.slopes,
+      vec![Slope { right: 3, down: 1 }, Slope { right: -1, down: 2 }]
+    );
+    assert_eq!(options.boundary, Boundary::Reflect);
+
+    assert!(parse_args(&args("--slope 3/0")).is_err());
+    assert!(parse_args(&args("--slope 3/1 --boundary bounce")).is_err());
+    assert!(parse_args(&args("--boundary stop")).is_err());
+
+    let options = parse_args(&args("--sweep 10/4 --boundary stop")).unwrap();
+    assert_eq!(options.sweep, Some(Slope { right: 10, down: 4 }));
+    assert_eq!(options.boundary, Boundary::Stop);
+    assert!(parse_args(&args("--sweep -1/4")).is_err());
+    assert!(parse_args(&args("--sweep 1000/1000")).is_ok());
+    assert_eq!(
+      parse_args(&args("--sweep 9223372036854775807/1")),
+      Err("--sweep bounds must be at most 1000/1000, got 9223372036854775807/1".to_string())
+    );
+    assert!(parse_args(&args("--sweep 1/1001")).is_err());
   }
 
   #[test]
-  fn it_multiplies_trees_in_path() {
-    let map = parse_input_file("test_input.txt");
-    let paths = vec![(1, 1), (3, 1), (5, 1), (7, 1), (1, 2)];
+  fn it_parses_render_options() {
+    let options = parse_args(&args("--render ppm --slope 1/2 --scale 3 --output map.ppm")).unwrap();
+    assert_eq!(options.render, Some(RenderFormat::Ppm));
+    assert_eq!(options.slopes, vec![Slope { right: 1, down: 2 }]);
+    assert_eq!(options.scale, 3);
+    assert_eq!(options.output, Some("map.ppm".to_string()));
 
     assert_eq!(
-      trees_for_paths_multiplied(&map, &paths),
-      336,
-      "correct multiplied tree count"
+      parse_args(&args("--render svg --boundary stop"))
+        .unwrap()
+        .scale,
+      DEFAULT_SCALE
+    );
+    assert_eq!(
+      parse_args(&args("--render ppm --output map.ppm"))
+        .unwrap()
+        .scale,
+      1
     );
+    assert!(parse_args(&args("--render ppm")).is_err());
+    assert!(parse_args(&args("--render gif")).is_err());
+    assert!(parse_args(&args("--render svg --scale 0")).is_err());
+    assert!(parse_args(&args("--output map.svg")).is_err());
   }
 }
@@ -0,0 +1,251 @@
+use std::fmt::Write;
+
+use shared::Grid;
+
+use crate::{ride, Boundary, Slope, Terrain};
+
+/// Path colours as RGB, picked by slope index and repeated when there are more slopes.
+const PALETTE: [(u8, u8, u8); 6] = [
+  (220, 50, 47),
+  (38, 139, 210),
+  (211, 54, 130),
+  (181, 137, 0),
+  (108, 113, 196),
+  (42, 161, 152),
+];
+/// ANSI foreground colours matching `PALETTE`.
+const ANSI_COLOURS: [u8; 6] = [31, 34, 35, 33, 95, 36];
+const OPEN: (u8, u8, u8) = (255, 255, 255);
+const TREE: (u8, u8, u8) = (46, 125, 50);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Pixel {
+  Terrain(Terrain),
+  /// A tree hit by the path of the slope with this index.
+  Hit(usize),
+  /// An open cell the path of the slope with this index passed through.
+  Miss(usize),
+}
+
+impl Pixel {
+  fn to_char(self) -> char {
+    match self {
+      Pixel::Terrain(Terrain::Open) => '.',
+      Pixel::Terrain(Terrain::Tree) => '#',
+      Pixel::Hit(_) => 'X',
+      Pixel::Miss(_) => 'O',
+    }
+  }
+}
+
+/// The map repeated sideways as often as the paths need, with every path drawn on top. Where
+/// paths cross, the later slope is drawn.
+pub fn overlay(map: &Grid<Terrain>, slopes: &[Slope], boundary: Boundary) -> Grid<Pixel> {
+  let width = map.width() as isize;
+
+  // Wrapping paths run off into copies of the map, so they are drawn at their unwrapped column
+  let paths = slopes
+    .iter()
+    .map(|slope| {
+      let ride = ride(map, *slope, boundary);
+      ride
+        .path
+        .iter()
+        .enumerate()
+        .map(|(step, (row, col))| match boundary {
+          Boundary::Wrap => (*row, step as isize * slope.right),
+          Boundary::Stop | Boundary::Reflect => (*row, *col as isize),
+        })
+        .collect::<Vec<(usize, isize)>>()
+    })
+    .collect::<Vec<Vec<(usize, isize)>>>();
+
+  let columns = paths.iter().flatten().map(|(_, col)| *col);
+  let first_copy = columns.clone().min().unwrap_or(0).div_euclid(width.max(1));
+  let last_copy = columns.max().unwrap_or(0).div_euclid(width.max(1));
+  let copies = (last_copy - first_copy + 1) as usize;
+
+  let mut canvas = Grid::new(
+    map.width() * copies,
+    map.height(),
+    Pixel::Terrain(Terrain::Open),
+  );
+  for ((row, col), terrain) in map.iter() {
+    for copy in 0..copies {
+      canvas[(row, copy * map.width() + col)] = Pixel::Terrain(*terrain);
+    }
+  }
+
+  for (i, path) in paths.iter().enumerate() {
+    for (row, col) in path.iter() {
+      let col = (col - first_copy * width) as usize;
+      canvas[(*row, col)] = match canvas[(*row, col)] {
+        Pixel::Terrain(Terrain::Tree) | Pixel::Hit(_) => Pixel::Hit(i),
+        Pixel::Terrain(Terrain::Open) | Pixel::Miss(_) => Pixel::Miss(i),
+      };
+    }
+  }
+
+  canvas
+}
+
+/// Text with each path coloured by ANSI escape codes, one line per row.
+pub fn to_ansi(canvas: &Grid<Pixel>) -> String {
+  let mut ansi = String::new();
+
+  for row in canvas.rows() {
+    for pixel in row.iter() {
+      match pixel {
+        Pixel::Hit(i) | Pixel::Miss(i) => {
+          let colour = ANSI_COLOURS[i % ANSI_COLOURS.len()];
+          let _ = write!(ansi, "\x1b[1;{}m{}\x1b[0m", colour, pixel.to_char());
+        }
+        Pixel::Terrain(_) => ansi.push(pixel.to_char()),
+      }
+    }
+    ansi.push('\n');
+  }
+
+  ansi
+}
+
+fn hex((r, g, b): (u8, u8, u8)) -> String {
+  format!("#{:02x}{:02x}{:02x}", r, g, b)
+}
+
+/// An SVG with one `scale` sized square per cell and the path letters drawn over them.
+pub fn to_svg(canvas: &Grid<Pixel>, scale: usize) -> String {
+  let mut svg = format!(
+    "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{}\" height=\"{}\" font-family=\"monospace\" font-size=\"{}\" text-anchor=\"middle\" dominant-baseline=\"central\">\n",
+    canvas.width() * scale,
+    canvas.height() * scale,
+    scale
+  );
+
+  for ((row, col), pixel) in canvas.iter() {
+    let (x, y) = (col * scale, row * scale);
+    let background = match pixel {
+      Pixel::Terrain(Terrain::Tree) | Pixel::Hit(_) => TREE,
+      Pixel::Terrain(Terrain::Open) | Pixel::Miss(_) => OPEN,
+    };
+    let _ = writeln!(
+      svg,
+      "<rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\" fill=\"{}\"/>",
+      x,
+      y,
+      scale,
+      scale,
+      hex(background)
+    );
+
+    if let Pixel::Hit(i) | Pixel::Miss(i) = pixel {
+      let _ = writeln!(
+        svg,
+        "<text x=\"{}\" y=\"{}\" fill=\"{}\" font-weight=\"bold\">{}</text>",
+        x as f64 + scale as f64 / 2.0,
+        y as f64 + scale as f64 / 2.0,
+        hex(PALETTE[i % PALETTE.len()]),
+        pixel.to_char()
+      );
+    }
+  }
+
+  svg.push_str("</svg>\n");
+  svg
+}
+
+/// A binary PPM (P6) with `scale` by `scale` pixels per cell. Hits are drawn as a cross and
+/// misses as a square outline in the path's colour, at a scale of 1 the cell is simply filled.
+pub fn to_ppm(canvas: &Grid<Pixel>, scale: usize) -> Vec<u8> {
+  let (width, height) = (canvas.width() * scale, canvas.height() * scale);
+  let mut ppm = format!("P6\n{} {}\n255\n", width, height).into_bytes();
+
+  for y in 0..height {
+    for x in 0..width {
+      let pixel = canvas[(y / scale, x / scale)];
+      let (dy, dx) = (y % scale, x % scale);
+      let last = scale - 1;
+
+      let (r, g, b) = match pixel {
+        Pixel::Hit(i) if dx == dy || dx == last - dy => PALETTE[i % PALETTE.len()],
+        Pixel::Miss(i) if dx == 0 || dy == 0 || dx == last || dy == last => {
+          PALETTE[i % PALETTE.len()]
+        }
+        Pixel::Terrain(Terrain::Tree) | Pixel::Hit(_) => TREE,
+        Pixel::Terrain(Terrain::Open) | Pixel::Miss(_) => OPEN,
+      };
+      ppm.extend_from_slice(&[r, g, b]);
+    }
+  }
+
+  ppm
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::parse_map;
+
+  fn map() -> Grid<Terrain> {
+    let lines = vec!["..#".to_string(), "#..".to_string(), ".#.".to_string()];
+    parse_map(&lines).unwrap()
+  }
+
+  fn text(canvas: &Grid<Pixel>) -> String {
+    canvas
+      .rows()
+      .map(|row| row.iter().map(|p| p.to_char()).collect::<String>())
+      .collect::<Vec<String>>()
+      .join("\n")
+  }
+
+  #[test]
+  fn it_overlays_paths_on_the_repeated_map() {
+    let slopes = vec![Slope::new(2, 1).unwrap()];
+    let canvas = overlay(&map(), &slopes, Boundary::Wrap);
+
+    assert_eq!(text(&canvas), "O.#..#\n#.O#..\n.#..X.");
+    assert_eq!(canvas[(2, 4)], Pixel::Hit(0));
+  }
+
+  #[test]
+  fn it_repeats_the_map_to_the_left() {
+    let slopes = vec![Slope::new(-1, 1).unwrap(), Slope::new(0, 1).unwrap()];
+    let canvas = overlay(&map(), &slopes, Boundary::Wrap);
+
+    assert_eq!(text(&canvas), "..#O.#\n#.OX..\n.X.O#.");
+    assert_eq!(canvas[(0, 3)], Pixel::Miss(1));
+    assert_eq!(canvas[(2, 1)], Pixel::Hit(0));
+  }
+
+  #[test]
+  fn it_keeps_bounded_paths_on_the_map() {
+    let slopes = vec![Slope::new(2, 1).unwrap()];
+    let canvas = overlay(&map(), &slopes, Boundary::Reflect);
+
+    assert_eq!(text(&canvas), "O.#\n#.O\nO#.");
+  }
+
+  #[test]
+  fn it_exports_images() {
+    let canvas = overlay(&map(), &[Slope::new(1, 1).unwrap()], Boundary::Stop);
+
+    let ansi = to_ansi(&canvas);
+    assert!(ansi.starts_with("\x1b[1;31mO\x1b[0m.#\n"));
+    assert_eq!(ansi.lines().count(), 3);
+
+    let svg = to_svg(&canvas, 10);
+    assert!(svg.starts_with("<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"30\" height=\"30\""));
+    assert_eq!(svg.matches("<rect").count(), 9);
+    assert_eq!(svg.matches("<text").count(), 3);
+
+    let ppm = to_ppm(&canvas, 3);
+    let header = b"P6\n9 9\n255\n";
+    assert_eq!(&ppm[..header.len()], header);
+    assert_eq!(ppm.len(), header.len() + 9 * 9 * 3);
+    // The centre of the miss in the top left cell is left open
+    let centre = header.len() + (9 + 1) * 3;
+    assert_eq!(&ppm[centre..centre + 3], &[255, 255, 255]);
+    assert_eq!(&ppm[header.len()..header.len() + 3], &[220, 50, 47]);
+  }
+}
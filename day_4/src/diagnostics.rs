@@ -0,0 +1,199 @@
+use std::collections::BTreeMap;
+use std::fmt;
+use std::ops::RangeInclusive;
+
+use shared::Record;
+
+use crate::passport::{Passport, PassportError};
+use crate::schema::{Schema, Violation};
+
+/// Why a field failed, without the values involved: the kind of a parse error or of a
+/// violation. Histograms count failures per field and reason.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Reason {
+  DuplicateKey,
+  MalformedToken,
+  StrayText,
+  Missing,
+  UnknownKey,
+  NotANumber,
+  NotAYear,
+  OutOfRange,
+  BadUnit,
+  PatternMismatch,
+  NotAllowed,
+}
+
+impl fmt::Display for Reason {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    let reason = match self {
+      Reason::DuplicateKey => "duplicate key",
+      Reason::MalformedToken => "malformed token",
+      Reason::StrayText => "stray text",
+      Reason::Missing => "missing",
+      Reason::UnknownKey => "unknown key",
+      Reason::NotANumber => "not a number",
+      Reason::NotAYear => "not a four digit year",
+      Reason::OutOfRange => "out of range",
+      Reason::BadUnit => "bad unit",
+      Reason::PatternMismatch => "does not match pattern",
+      Reason::NotAllowed => "not an allowed value",
+    };
+
+    write!(f, "{}", reason)
+  }
+}
+
+/// Every violation in one passport, with where the passport is in the batch file. A passport
+/// with bad tokens has the first of their parse errors, and its other fields are still
+/// checked. Violations that do not invalidate the passport, such as unknown keys, are still
+/// listed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Diagnostics {
+  /// 1-based position of the passport in the batch.
+  pub index: usize,
+  pub lines: RangeInclusive<usize>,
+  pub error: Option<PassportError>,
+  pub violations: Vec<Violation>,
+}
+
+impl Diagnostics {
+  pub fn is_valid(&self) -> bool {
+    self.error.is_none() && self.violations.iter().all(|v| !v.invalidates())
+  }
+}
+
+pub fn diagnose(schema: &Schema, records: &[Record]) -> Vec<Diagnostics> {
+  records
+    .iter()
+    .enumerate()
+    .map(|(i, record)| {
+      let (passport, error) = Passport::parse_partial(record);
+      // A field whose token failed to parse is reported by the error, not as missing too
+      let unparsed = error.as_ref().and_then(|error| error.key());
+      let violations = schema
+        .check(&passport)
+        .into_iter()
+        .filter(|v| !(v.reason() == Reason::Missing && Some(v.key()) == unparsed))
+        .collect();
+
+      Diagnostics {
+        index: i + 1,
+        lines: record.line_range(),
+        error,
+        violations,
+      }
+    })
+    .collect()
+}
+
+/// How often each field failed for each reason across a batch, most common first and then
+/// by field and reason. Parse errors without a key are counted under `-`.
+pub fn histogram(diagnostics: &[Diagnostics]) -> Vec<((String, Reason), usize)> {
+  let mut counts = BTreeMap::new();
+  for d in diagnostics.iter() {
+    let errors = d
+      .error
+      .iter()
+      .map(|error| (error.key().unwrap_or("-"), error.reason()));
+    let violations = d.violations.iter().map(|v| (v.key(), v.reason()));
+
+    for (key, reason) in errors.chain(violations) {
+      *counts.entry((key.to_string(), reason)).or_insert(0) += 1;
+    }
+  }
+
+  let mut counts = counts.into_iter().collect::<Vec<_>>();
+  counts.sort_by_key(|(_, count)| std::cmp::Reverse(*count));
+  counts
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn it_diagnoses_each_passport() {
+    let lines = shared::try_read_file("test_input.txt").unwrap();
+    let diagnostics = diagnose(&Schema::puzzle(), &shared::parse_numbered_records(&lines));
+
+    assert_eq!(
+      diagnostics
+        .iter()
+        .map(|d| (d.index, d.lines.clone(), d.is_valid()))
+        .collect::<Vec<_>>(),
+      vec![
+        (1, 1..=2, true),
+        (2, 4..=5, false),
+        (3, 7..=10, true),
+        (4, 12..=13, false),
+      ]
+    );
+    assert_eq!(
+      diagnostics[1]
+        .violations
+        .iter()
+        .map(|v| v.to_string())
+        .collect::<Vec<String>>(),
+      vec!["hgt: missing"]
+    );
+  }
+
+  #[test]
+  fn it_checks_the_parsed_fields_of_malformed_passports() {
+    let lines = vec![
+      "byr:1937 byr:1938 iyr:2017 eyr:2020 hgt:183cm hcl:#fffffd ecl:xyz pid:860033327".to_string(),
+      "".to_string(),
+      "hgt: byr:1937 iyr:2017 eyr:2020 hcl:#fffffd ecl:gry pid:860033327".to_string(),
+    ];
+    let diagnostics = diagnose(&Schema::puzzle(), &shared::parse_numbered_records(&lines));
+
+    assert!(diagnostics.iter().all(|d| !d.is_valid()));
+    assert_eq!(
+      diagnostics[0]
+        .violations
+        .iter()
+        .map(|v| (v.key(), v.reason()))
+        .collect::<Vec<_>>(),
+      vec![("ecl", Reason::NotAllowed)]
+    );
+    assert_eq!(
+      diagnostics[1].error.as_ref().unwrap().to_string(),
+      "line 3, column 1: malformed token \"hgt:\": missing value"
+    );
+    assert!(diagnostics[1].violations.is_empty());
+    assert_eq!(
+      histogram(&diagnostics),
+      vec![
+        (("byr".to_string(), Reason::DuplicateKey), 1),
+        (("ecl".to_string(), Reason::NotAllowed), 1),
+        (("hgt".to_string(), Reason::MalformedToken), 1),
+      ]
+    );
+  }
+
+  #[test]
+  fn it_counts_failure_reasons() {
+    let lines = shared::try_read_file("invalid_format_input.txt").unwrap();
+    let diagnostics = diagnose(&Schema::puzzle(), &shared::parse_numbered_records(&lines));
+    let histogram = histogram(&diagnostics);
+
+    assert!(diagnostics.iter().all(|d| !d.is_valid()));
+    assert_eq!(
+      histogram
+        .iter()
+        .map(|((key, reason), count)| (key.as_str(), *reason, *count))
+        .collect::<Vec<_>>(),
+      vec![
+        ("eyr", Reason::OutOfRange, 3),
+        ("hcl", Reason::PatternMismatch, 2),
+        ("pid", Reason::PatternMismatch, 2),
+        ("byr", Reason::OutOfRange, 1),
+        ("ecl", Reason::NotAllowed, 1),
+        ("hgt", Reason::OutOfRange, 1),
+        ("hgt", Reason::BadUnit, 1),
+        ("iyr", Reason::OutOfRange, 1),
+      ]
+    );
+  }
+}
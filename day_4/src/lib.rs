@@ -0,0 +1,175 @@
+use std::sync::OnceLock;
+
+use shared::{InputError, Solver};
+
+pub mod diagnostics;
+pub mod passport;
+pub mod repair;
+pub mod schema;
+
+use passport::Passport;
+use schema::Schema;
+
+pub fn parse_input_file(filename: &str) -> Result<Vec<Passport>, InputError> {
+  let lines = shared::try_read_file(filename)?;
+
+  parse_entries(&lines)
+}
+
+pub fn parse_entries(lines: &[String]) -> Result<Vec<Passport>, InputError> {
+  shared::parse_numbered_records(lines)
+    .iter()
+    .map(|record| {
+      Passport::parse(record).map_err(|err| {
+        InputError::parse(
+          err.line,
+          &record.lines[err.line - record.start],
+          format!("column {}: {}", err.column, err.kind),
+        )
+      })
+    })
+    .collect()
+}
+
+/// The puzzle schema, built on first use and shared from then on.
+fn puzzle_schema() -> &'static Schema {
+  static SCHEMA: OnceLock<Schema> = OnceLock::new();
+
+  SCHEMA.get_or_init(Schema::puzzle)
+}
+
+pub fn has_all_required_fields(passport: &Passport) -> bool {
+  puzzle_schema().has_required_fields(passport)
+}
+
+pub fn has_valid_field_data(passport: &Passport) -> bool {
+  puzzle_schema().is_valid(passport)
+}
+
+pub fn count_valid_entries(
+  passports: &[Passport],
+  validate_fn: &dyn Fn(&Passport) -> bool,
+) -> usize {
+  passports
+    .iter()
+    .filter(|passport| validate_fn(passport))
+    .count()
+}
+
+pub struct Day4;
+
+impl Solver for Day4 {
+  type Input = Vec<Passport>;
+  type Answer = usize;
+
+  fn day(&self) -> u8 {
+    4
+  }
+
+  fn input_dir(&self) -> &'static str {
+    env!("CARGO_MANIFEST_DIR")
+  }
+
+  fn parse(&self, lines: &[String]) -> Result<Vec<Passport>, InputError> {
+    parse_entries(lines)
+  }
+
+  fn part_one(&self, passports: &Vec<Passport>) -> Option<usize> {
+    Some(count_valid_entries(passports, &has_all_required_fields))
+  }
+
+  fn part_two(&self, passports: &Vec<Passport>) -> Option<usize> {
+    Some(count_valid_entries(passports, &has_valid_field_data))
+  }
+}
+
+#[cfg(test)]
+#[allow(clippy::bool_assert_comparison)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn it_parses_correct_number_of_entries() {
+    assert_eq!(
+      parse_input_file("test_input.txt").unwrap().len(),
+      4,
+      "correct number of entries parsed"
+    );
+  }
+
+  #[test]
+  fn it_checks_entry_validity() {
+    let entries = parse_input_file("test_input.txt").unwrap();
+
+    assert_eq!(
+      has_all_required_fields(&entries[0]),
+      true,
+      "valid, all fileds"
+    );
+    assert_eq!(
+      has_all_required_fields(&entries[1]),
+      false,
+      "invalid, missing hgt"
+    );
+    assert_eq!(
+      has_all_required_fields(&entries[2]),
+      true,
+      "valid, missing cid"
+    );
+    assert_eq!(
+      has_all_required_fields(&entries[3]),
+      false,
+      "invalid, missing cid and byr"
+    );
+  }
+
+  #[test]
+  fn it_counts_valid_entries() {
+    let entries = parse_input_file("test_input.txt").unwrap();
+    assert_eq!(
+      count_valid_entries(&entries, &has_all_required_fields),
+      2,
+      "valid entries in test data"
+    )
+  }
+
+  #[test]
+  fn it_reports_malformed_passports() {
+    let lines = vec![
+      "byr:1937 iyr:2017".to_string(),
+      "".to_string(),
+      "hgt:cm ecl:gry".to_string(),
+      "hgt:150cm".to_string(),
+    ];
+    assert_eq!(
+      parse_entries(&lines).unwrap_err().to_string(),
+      "line 4: could not parse \"hgt:150cm\": column 1: duplicate key \"hgt\", first given at line 3, column 1"
+    );
+  }
+
+  #[test]
+  fn it_checks_validity_of_entries() {
+    let entries = parse_input_file("test_input.txt").unwrap();
+    has_valid_field_data(&entries[0]);
+  }
+
+  #[test]
+  fn it_validates_invalid_entry_data() {
+    let invalid_entries = parse_input_file("invalid_format_input.txt").unwrap();
+    assert_eq!(invalid_entries.len(), 4);
+
+    for entry in invalid_entries {
+      assert_eq!(has_valid_field_data(&entry), false, "invalid entry data");
+    }
+  }
+
+  #[test]
+  fn it_validates_valid_entry_data() {
+    let valid_entries = parse_input_file("valid_format_input.txt").unwrap();
+    assert_eq!(valid_entries.len(), 4);
+
+    for entry in valid_entries {
+      assert_eq!(has_valid_field_data(&entry), true, "valid entry data");
+    }
+  }
+}
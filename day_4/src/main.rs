@@ -1,225 +1,171 @@
-use regex::Regex;
-use std::collections::HashMap;
-use std::fs::File;
-use std::io::prelude::*;
+use std::env;
+use std::fs;
+use std::process;
+
+use day_4::passport::Passport;
+use day_4::schema::Schema;
+use day_4::Day4;
+use day_4::{diagnostics, repair};
+use shared::{InputSource, Part, Puzzle};
+
+const USAGE: &str = "Usage: day_4 [INPUT] [--rules FILE] [--diagnose | --fix OUTPUT]";
+
+#[derive(Debug, Default, PartialEq)]
+struct Options {
+  input: Option<String>,
+  rules: Option<String>,
+  diagnose: bool,
+  fix: Option<String>,
+}
 
-fn parse_input_file(filename: &str) -> Vec<String> {
-  let mut file = File::open(filename).unwrap();
-  let mut contents = String::new();
+fn parse_args(args: &[String]) -> Result<Options, String> {
+  let mut options = Options::default();
 
-  file.read_to_string(&mut contents).unwrap();
+  options.input = shared::parse_input_and_flags(args, |flag, args| {
+    match flag {
+      "--rules" => options.rules = Some(args.value(flag, "a path")?.to_string()),
+      "--diagnose" => options.diagnose = true,
+      "--fix" => options.fix = Some(args.value(flag, "an output path")?.to_string()),
+      _ => return Ok(false),
+    }
 
-  let entries = contents
-    .split("\n\n")
-    .map(|entry| entry.to_string())
-    .collect::<Vec<String>>();
-  entries
-}
+    Ok(true)
+  })?;
 
-fn has_all_required_fields(entry: &str) -> bool {
-  let required_fields = ["byr", "iyr", "eyr", "hgt", "hcl", "ecl", "pid"];
+  if options.diagnose && options.fix.is_some() {
+    return Err("--diagnose and --fix can not be combined".to_string());
+  }
 
-  required_fields.iter().all(|field| entry.contains(field))
+  Ok(options)
 }
 
-fn has_valid_field_data(entry: &str) -> bool {
-  let cleaned_entry = entry.replace("\n", " ");
-
-  let fields: HashMap<&str, &str> =
-    cleaned_entry
-      .split(" ")
-      .fold(HashMap::new(), |mut map, raw_entry| {
-        let kv_pairs = raw_entry.split(" ");
-
-        for kv_pair in kv_pairs {
-          let kv = kv_pair.split(":").collect::<Vec<&str>>();
-          if kv.len() == 2 {
-            map.insert(kv[0], kv[1]);
-          }
-        }
-
-        map
-      });
-
-  match fields.get("byr") {
-    Some(byr) => match (*byr).parse::<usize>() {
-      Ok(byr_value) => {
-        if byr_value < 1920 || byr_value > 2002 {
-          return false;
-        }
-      }
-      _ => return false,
-    },
-    _ => return false,
-  }
-
-  match fields.get("iyr") {
-    Some(iyr) => match (*iyr).parse::<usize>() {
-      Ok(iyr_value) => {
-        if iyr_value < 2010 || iyr_value > 2020 {
-          return false;
-        }
-      }
-      _ => return false,
-    },
-    _ => return false,
+fn main() {
+  let args = env::args().skip(1).collect::<Vec<String>>();
+  let options = parse_args(&args).unwrap_or_else(|err| {
+    eprintln!("Error: {}\n\n{}", err, USAGE);
+    process::exit(2);
+  });
+  let input = InputSource::resolve(options.input.as_deref(), Day4.input_dir());
+
+  if options.rules.is_none() && !options.diagnose && options.fix.is_none() {
+    shared::unwrap_or_exit(shared::run(&Day4, &input, &Part::ALL));
+    return;
   }
 
-  match fields.get("eyr") {
-    Some(eyr) => match (*eyr).parse::<usize>() {
-      Ok(eyr_value) => {
-        if eyr_value < 2010 || eyr_value > 2030 {
-          return false;
-        }
-      }
-      _ => return false,
-    },
-    _ => return false,
-  }
+  let schema = match &options.rules {
+    Some(rules) => Schema::load(rules).unwrap_or_else(|err| {
+      eprintln!("Error: {}", err);
+      process::exit(1);
+    }),
+    None => Schema::puzzle(),
+  };
+  let text = shared::unwrap_or_exit(input.read_to_string());
+  let lines = text
+    .lines()
+    .map(|line| line.to_string())
+    .collect::<Vec<String>>();
 
-  match fields.get("hgt") {
-    Some(hgt) => {
-      let hgt_val = *hgt;
-      if hgt_val.contains("cm") {
-        let hgt_num = hgt_val.replace("cm", "").parse::<usize>().unwrap();
-        if hgt_num < 150 || hgt_num > 193 {
-          return false;
-        }
-      } else if hgt_val.contains("in") {
-        let hgt_num = hgt_val.replace("in", "").parse::<usize>().unwrap();
-        if hgt_num < 59 || hgt_num > 76 {
-          return false;
-        }
-      } else {
-        return false;
-      }
-    }
-    _ => return false,
+  match &options.fix {
+    Some(output) => fix(&schema, &text, output),
+    None if options.diagnose => print_diagnostics(&schema, &lines),
+    None => print_counts(&schema, &lines),
   }
+}
 
-  match fields.get("hcl") {
-    Some(hcl) => {
-      let hcl_re = Regex::new(r"^#[0-9a-f]{6}$").unwrap();
-      if !hcl_re.is_match(*hcl) {
-        return false;
-      }
-    }
-    _ => return false,
-  }
+fn fix(schema: &Schema, text: &str, output: &str) {
+  let (fixed, changes) = repair::fix_text(schema, text);
 
-  match fields.get("ecl") {
-    Some(ecl) => {
-      let valid_eye_colors = ["amb", "blu", "brn", "gry", "grn", "hzl", "oth"];
-      if !valid_eye_colors.contains(&ecl) {
-        return false;
-      }
-    }
-    _ => return false,
+  if let Err(err) = fs::write(output, fixed) {
+    eprintln!("Error: could not write {}: {}", output, err);
+    process::exit(1);
   }
 
-  match fields.get("pid") {
-    Some(pid) => {
-      let pid_re = Regex::new(r"^[0-9]{9}$").unwrap();
-      if !pid_re.is_match(pid) {
-        return false;
-      }
-    }
-    _ => return false,
+  for change in changes.iter() {
+    println!(
+      "line {}, column {}: {} {:?} -> {:?} ({})",
+      change.line, change.column, change.fix.key, change.fix.from, change.fix.to, change.fix.reason
+    );
   }
-
-  return true;
+  println!("{} changes written to {}", changes.len(), output);
 }
 
-fn count_valid_entries(entries: &Vec<String>, validate_fn: &dyn Fn(&str) -> bool) -> usize {
-  entries.iter().filter(|entry| validate_fn(entry)).count()
+fn print_counts(schema: &Schema, lines: &[String]) {
+  let passports = shared::unwrap_or_exit(day_4::parse_entries(lines));
+
+  let complete = passports
+    .iter()
+    .filter(|p| schema.has_required_fields(p))
+    .count();
+  let valid = passports.iter().filter(|p| schema.is_valid(p)).count();
+  println!("{} passports", passports.len());
+  println!("{} have every required field", complete);
+  println!("{} are valid", valid);
 }
 
-fn main() {
-  let entries = parse_input_file("input.txt");
-  let num_valid_field_entries = count_valid_entries(&entries, &has_all_required_fields);
-  println!(
-    "Number of entries with all required keys: {}",
-    num_valid_field_entries
-  );
-  let num_valid_data_entries = count_valid_entries(&entries, &has_valid_field_data);
+fn print_diagnostics(schema: &Schema, lines: &[String]) {
+  let records = shared::parse_numbered_records(lines);
+  let diagnostics = diagnostics::diagnose(schema, &records);
+
+  let reported = diagnostics
+    .iter()
+    .filter(|d| d.error.is_some() || !d.violations.is_empty())
+    .collect::<Vec<_>>();
+  for passport in reported.iter() {
+    println!(
+      "Passport {} (lines {}-{}):",
+      passport.index,
+      passport.lines.start(),
+      passport.lines.end()
+    );
+    if let Some(error) = &passport.error {
+      println!("  {}", error);
+    }
+    for violation in passport.violations.iter() {
+      println!("  {}", violation);
+    }
+    let (parsed, _) = Passport::parse_partial(&records[passport.index - 1]);
+    for fix in repair::suggest(schema, &parsed).iter() {
+      println!(
+        "  suggested fix for {}: {:?} -> {:?} ({})",
+        fix.key, fix.from, fix.to, fix.reason
+      );
+    }
+  }
+
   println!(
-    "Number of entries with valid data: {}",
-    num_valid_data_entries
+    "{} of {} passports are invalid",
+    diagnostics.iter().filter(|d| !d.is_valid()).count(),
+    diagnostics.len()
   );
+  let histogram = diagnostics::histogram(&diagnostics);
+  if !histogram.is_empty() {
+    println!("Failure reasons:");
+    for ((key, reason), count) in histogram.iter() {
+      println!("{:>6}  {}: {}", count, key, reason);
+    }
+  }
 }
 
 #[cfg(test)]
 mod tests {
   use super::*;
+  use shared::split_args as args;
 
   #[test]
-  fn it_parses_correct_number_of_entries() {
-    assert_eq!(
-      parse_input_file("test_input.txt").len(),
-      4,
-      "correct number of entries parsed"
-    );
-  }
+  fn it_parses_the_rules_flag() {
+    assert_eq!(parse_args(&args("")).unwrap(), Options::default());
 
-  #[test]
-  fn it_checks_entry_validity() {
-    let entries = parse_input_file("test_input.txt");
+    let options = parse_args(&args("batch.txt --rules rules.json")).unwrap();
+    assert_eq!(options.input, Some("batch.txt".to_string()));
+    assert_eq!(options.rules, Some("rules.json".to_string()));
 
-    assert_eq!(
-      has_all_required_fields(&entries[0]),
-      true,
-      "valid, all fileds"
-    );
-    assert_eq!(
-      has_all_required_fields(&entries[1]),
-      false,
-      "invalid, missing hgt"
-    );
-    assert_eq!(
-      has_all_required_fields(&entries[2]),
-      true,
-      "valid, missing cid"
-    );
-    assert_eq!(
-      has_all_required_fields(&entries[3]),
-      false,
-      "invalid, missing cid and byr"
-    );
-  }
+    assert!(parse_args(&args("--rules")).is_err());
+    assert!(parse_args(&args("--diagnose")).unwrap().diagnose);
 
-  #[test]
-  fn it_counts_valid_entries() {
-    let entries = parse_input_file("test_input.txt");
-    assert_eq!(
-      count_valid_entries(&entries, &has_all_required_fields),
-      2,
-      "valid entries in test data"
-    )
-  }
-
-  #[test]
-  fn it_checks_validity_of_entries() {
-    let entries = parse_input_file("test_input.txt");
-    has_valid_field_data(&entries[0]);
-  }
-
-  #[test]
-  fn it_validates_invalid_entry_data() {
-    let invalid_entries = parse_input_file("invalid_format_input.txt");
-    assert_eq!(invalid_entries.len(), 4);
-
-    for entry in invalid_entries {
-      assert_eq!(has_valid_field_data(&entry), false, "invalid entry data");
-    }
-  }
-
-  #[test]
-  fn it_validates_valid_entry_data() {
-    let valid_entries = parse_input_file("valid_format_input.txt");
-    assert_eq!(valid_entries.len(), 4);
-
-    for entry in valid_entries {
-      assert_eq!(has_valid_field_data(&entry), true, "valid entry data");
-    }
+    let options = parse_args(&args("batch.txt --fix fixed.txt")).unwrap();
+    assert_eq!(options.fix, Some("fixed.txt".to_string()));
+    assert!(parse_args(&args("--fix")).is_err());
+    assert!(parse_args(&args("--diagnose --fix fixed.txt")).is_err());
+    assert!(parse_args(&args("--strict")).is_err());
   }
 }
@@ -0,0 +1,304 @@
+use std::collections::BTreeMap;
+use std::error::Error;
+use std::fmt;
+use std::ops::RangeInclusive;
+use std::str::FromStr;
+
+use shared::Record;
+
+use crate::diagnostics::Reason;
+
+/// A field's value and where its `key:value` token starts, as 1-based line and column.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Field {
+  pub value: String,
+  pub line: usize,
+  pub column: usize,
+}
+
+/// A passport's fields keyed by field name.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Passport {
+  pub lines: RangeInclusive<usize>,
+  pub fields: BTreeMap<String, Field>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PassportErrorKind {
+  /// A key that was already given earlier in the passport, at `first`.
+  DuplicateKey { key: String, first: (usize, usize) },
+  /// A token with a colon that is not a single `key:value` pair.
+  MalformedToken { token: String, reason: &'static str },
+  /// A token without a colon.
+  StrayText(String),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PassportError {
+  pub line: usize,
+  pub column: usize,
+  pub kind: PassportErrorKind,
+}
+
+impl PassportError {
+  /// The key the error is about, if the token had one.
+  pub fn key(&self) -> Option<&str> {
+    match &self.kind {
+      PassportErrorKind::DuplicateKey { key, .. } => Some(key),
+      PassportErrorKind::MalformedToken { token, .. } => {
+        token.split(':').next().filter(|key| !key.is_empty())
+      }
+      PassportErrorKind::StrayText(_) => None,
+    }
+  }
+
+  pub fn reason(&self) -> Reason {
+    match self.kind {
+      PassportErrorKind::DuplicateKey { .. } => Reason::DuplicateKey,
+      PassportErrorKind::MalformedToken { .. } => Reason::MalformedToken,
+      PassportErrorKind::StrayText(_) => Reason::StrayText,
+    }
+  }
+}
+
+impl fmt::Display for PassportErrorKind {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    match self {
+      PassportErrorKind::DuplicateKey { key, first } => write!(
+        f,
+        "duplicate key {:?}, first given at line {}, column {}",
+        key, first.0, first.1
+      ),
+      PassportErrorKind::MalformedToken { token, reason } => {
+        write!(f, "malformed token {:?}: {}", token, reason)
+      }
+      PassportErrorKind::StrayText(text) => write!(f, "stray text {:?}", text),
+    }
+  }
+}
+
+impl fmt::Display for PassportError {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    write!(
+      f,
+      "line {}, column {}: {}",
+      self.line, self.column, self.kind
+    )
+  }
+}
+
+impl Error for PassportError {}
+
+/// The whitespace separated tokens of a line with their 1-based (char) columns.
+fn tokens(line: &str) -> Vec<(usize, &str)> {
+  let mut tokens = vec![];
+  let mut start = None;
+
+  for (column, (i, c)) in line.char_indices().enumerate() {
+    match (start, c.is_whitespace()) {
+      (None, false) => start = Some((column + 1, i)),
+      (Some((token_column, token_start)), true) => {
+        tokens.push((token_column, &line[token_start..i]));
+        start = None;
+      }
+      _ => {}
+    }
+  }
+  if let Some((token_column, token_start)) = start {
+    tokens.push((token_column, &line[token_start..]));
+  }
+
+  tokens
+}
+
+fn parse_token(token: &str) -> Result<(&str, &str), PassportErrorKind> {
+  let malformed = |reason| PassportErrorKind::MalformedToken {
+    token: token.to_string(),
+    reason,
+  };
+
+  let parts = token.split(':').collect::<Vec<&str>>();
+  match parts.as_slice() {
+    [_] => Err(PassportErrorKind::StrayText(token.to_string())),
+    ["", _] => Err(malformed("missing key")),
+    [_, ""] => Err(malformed("missing value")),
+    [key, _] if !key.bytes().all(|b| b.is_ascii_alphanumeric()) => {
+      Err(malformed("keys may only contain letters and digits"))
+    }
+    [key, value] => Ok((key, value)),
+    _ => Err(malformed("more than one ':'")),
+  }
+}
+
+impl Passport {
+  /// Parses the `key:value` tokens of a record, failing on the first bad token.
+  pub fn parse(record: &Record) -> Result<Passport, PassportError> {
+    match Passport::parse_partial(record) {
+      (passport, None) => Ok(passport),
+      (_, Some(error)) => Err(error),
+    }
+  }
+
+  /// Parses the good `key:value` tokens of a record and skips the bad ones, returning the
+  /// error of the first bad token alongside the fields that did parse. Of duplicate keys,
+  /// the first is kept.
+  pub fn parse_partial(record: &Record) -> (Passport, Option<PassportError>) {
+    let mut fields = BTreeMap::<String, Field>::new();
+    let mut error = None;
+
+    for (line, text) in record.line_range().zip(record.lines.iter()) {
+      for (column, token) in tokens(text) {
+        let kind = match parse_token(token) {
+          Ok((key, _)) if fields.contains_key(key) => PassportErrorKind::DuplicateKey {
+            key: key.to_string(),
+            first: (fields[key].line, fields[key].column),
+          },
+          Ok((key, value)) => {
+            fields.insert(
+              key.to_string(),
+              Field {
+                value: value.to_string(),
+                line,
+                column,
+              },
+            );
+            continue;
+          }
+          Err(kind) => kind,
+        };
+
+        if error.is_none() {
+          error = Some(PassportError { line, column, kind });
+        }
+      }
+    }
+
+    let passport = Passport {
+      lines: record.line_range(),
+      fields,
+    };
+    (passport, error)
+  }
+
+  pub fn get(&self, key: &str) -> Option<&str> {
+    self.fields.get(key).map(|field| field.value.as_str())
+  }
+
+  pub fn contains_key(&self, key: &str) -> bool {
+    self.fields.contains_key(key)
+  }
+
+  pub fn keys(&self) -> impl Iterator<Item = &str> + '_ {
+    self.fields.keys().map(|key| key.as_str())
+  }
+}
+
+/// Parses a single passport, numbering its lines from 1.
+impl FromStr for Passport {
+  type Err = PassportError;
+
+  fn from_str(text: &str) -> Result<Passport, PassportError> {
+    Passport::parse(&Record {
+      start: 1,
+      lines: text.lines().map(|line| line.to_string()).collect(),
+    })
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn it_parses_fields_with_positions() {
+    let passport = "ecl:gry  pid:860033327\nhcl:#fffffd"
+      .parse::<Passport>()
+      .unwrap();
+
+    assert_eq!(passport.get("ecl"), Some("gry"));
+    assert_eq!(passport.get("byr"), None);
+    assert_eq!(
+      passport.keys().collect::<Vec<&str>>(),
+      vec!["ecl", "hcl", "pid"]
+    );
+    assert_eq!(
+      passport.fields["pid"],
+      Field {
+        value: "860033327".to_string(),
+        line: 1,
+        column: 10
+      }
+    );
+    assert_eq!(
+      (passport.fields["hcl"].line, passport.fields["hcl"].column),
+      (2, 1)
+    );
+  }
+
+  #[test]
+  fn it_does_not_mistake_values_for_keys() {
+    let passport = "hcl:#byr123".parse::<Passport>().unwrap();
+
+    assert!(passport.contains_key("hcl"));
+    assert!(!passport.contains_key("byr"));
+  }
+
+  #[test]
+  fn it_rejects_bad_tokens_with_positions() {
+    let error = |text: &str| text.parse::<Passport>().unwrap_err().to_string();
+
+    assert_eq!(
+      error("byr:1937 iyr:2017\necl:gry byr:1938"),
+      "line 2, column 9: duplicate key \"byr\", first given at line 1, column 1"
+    );
+    assert_eq!(
+      error("byr:1937 hgt:"),
+      "line 1, column 10: malformed token \"hgt:\": missing value"
+    );
+    assert_eq!(
+      error(":1937"),
+      "line 1, column 1: malformed token \":1937\": missing key"
+    );
+    assert_eq!(
+      error("a:b:c"),
+      "line 1, column 1: malformed token \"a:b:c\": more than one ':'"
+    );
+    assert_eq!(
+      error("b-r:1"),
+      "line 1, column 1: malformed token \"b-r:1\": keys may only contain letters and digits"
+    );
+    assert_eq!(
+      error("ecl:gry\n  oops"),
+      "line 2, column 3: stray text \"oops\""
+    );
+  }
+
+  #[test]
+  fn it_numbers_lines_from_the_record() {
+    let record = Record {
+      start: 7,
+      lines: vec!["ecl:gry".to_string(), "ecl:brn".to_string()],
+    };
+    let error = Passport::parse(&record).unwrap_err();
+
+    assert_eq!((error.line, error.column), (8, 1));
+    assert_eq!(error.key(), Some("ecl"));
+    assert_eq!(error.reason(), Reason::DuplicateKey);
+  }
+
+  #[test]
+  fn it_keeps_the_fields_around_bad_tokens() {
+    let record = Record {
+      start: 1,
+      lines: vec!["ecl:gry hgt: pid:1".to_string(), "ecl:brn oops".to_string()],
+    };
+    let (passport, error) = Passport::parse_partial(&record);
+
+    assert_eq!(passport.keys().collect::<Vec<&str>>(), vec!["ecl", "pid"]);
+    assert_eq!(passport.get("ecl"), Some("gry"));
+    assert_eq!(
+      error.unwrap().to_string(),
+      "line 1, column 9: malformed token \"hgt:\": missing value"
+    );
+  }
+}
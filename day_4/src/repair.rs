@@ -0,0 +1,270 @@
+use crate::passport::Passport;
+use crate::schema::{Rule, Schema};
+
+/// A change to one field that makes it pass its rule.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Fix {
+  pub key: String,
+  pub from: String,
+  pub to: String,
+  pub reason: &'static str,
+}
+
+/// A fix applied to a batch file, at the 1-based line and column of the field's token.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Change {
+  pub line: usize,
+  pub column: usize,
+  pub fix: Fix,
+}
+
+/// The longest zero-padded value tried when padding a number to fit a pattern.
+const MAX_PADDED_LENGTH: usize = 32;
+
+/// Levenshtein distance between two strings, counted in chars.
+fn edit_distance(a: &str, b: &str) -> usize {
+  let b = b.chars().collect::<Vec<char>>();
+  let mut previous = (0..=b.len()).collect::<Vec<usize>>();
+
+  for (i, a) in a.chars().enumerate() {
+    let mut current = vec![i + 1];
+    for (j, b) in b.iter().enumerate() {
+      let substitution = previous[j] + if a == *b { 0 } else { 1 };
+      current.push(substitution.min(previous[j + 1] + 1).min(current[j] + 1));
+    }
+    previous = current;
+  }
+
+  previous[b.len()]
+}
+
+/// Candidate replacements for a value that breaks `rule`, in the order they are tried.
+fn candidates(rule: &Rule, value: &str) -> Vec<(String, &'static str)> {
+  let mut candidates = vec![];
+
+  let lower = value.to_lowercase();
+  if lower != value {
+    candidates.push((lower, "lower-cased"));
+  }
+
+  match rule {
+    Rule::Units { units } if !value.is_empty() && value.bytes().all(|b| b.is_ascii_digit()) => {
+      let number = value.parse::<u32>().ok();
+      let fitting = units
+        .iter()
+        .filter(|(_, (min, max))| number.is_some_and(|n| (*min..=*max).contains(&n)))
+        .collect::<Vec<_>>();
+
+      // Only add a unit when exactly one unit's range fits the number
+      if let [(unit, _)] = fitting.as_slice() {
+        candidates.push((
+          format!("{}{}", value, unit),
+          "added the only unit that fits",
+        ));
+      }
+    }
+    Rule::Regex { .. } if !value.is_empty() && value.bytes().all(|b| b.is_ascii_digit()) => {
+      candidates.extend(
+        (value.len() + 1..=MAX_PADDED_LENGTH)
+          .map(|length| (format!("{:0>1$}", value, length), "zero-padded")),
+      );
+    }
+    Rule::OneOf { values } => {
+      let distances = values
+        .iter()
+        .map(|v| (edit_distance(&value.to_lowercase(), v), v))
+        .filter(|(distance, _)| *distance == 1)
+        .collect::<Vec<_>>();
+
+      // A value one edit away from several allowed values is ambiguous
+      if let [(_, closest)] = distances.as_slice() {
+        candidates.push((closest.to_string(), "closest allowed value"));
+      }
+    }
+    _ => {}
+  }
+
+  candidates
+}
+
+/// Fixes for the fields of a passport that break their rules, where a change can be made
+/// without guessing: the first candidate that passes the rule is suggested.
+pub fn suggest(schema: &Schema, passport: &Passport) -> Vec<Fix> {
+  schema
+    .fields
+    .iter()
+    .filter_map(|field| {
+      let value = passport.get(&field.key)?;
+      if field.rule.matches(value) {
+        return None;
+      }
+
+      candidates(&field.rule, value)
+        .into_iter()
+        .find(|(candidate, _)| field.rule.matches(candidate))
+        .map(|(to, reason)| Fix {
+          key: field.key.to_string(),
+          from: value.to_string(),
+          to,
+          reason,
+        })
+    })
+    .collect()
+}
+
+/// Applies every suggested fix to a batch file, leaving everything else, including passports
+/// that do not parse, exactly as it was. Returns the corrected lines and the changes made.
+pub fn fix_batch(schema: &Schema, lines: &[String]) -> (Vec<String>, Vec<Change>) {
+  let mut lines = lines
+    .iter()
+    .flat_map(|line| line.split('\n'))
+    .map(|line| line.to_string())
+    .collect::<Vec<String>>();
+  let mut changes = vec![];
+
+  for record in shared::parse_numbered_records(&lines).iter() {
+    if let Ok(passport) = Passport::parse(record) {
+      changes.extend(suggest(schema, &passport).into_iter().map(|fix| {
+        let field = &passport.fields[&fix.key];
+        Change {
+          line: field.line,
+          column: field.column,
+          fix,
+        }
+      }));
+    }
+  }
+
+  // Later tokens on a line are replaced first so that earlier columns stay valid
+  let mut ordered = changes.iter().collect::<Vec<&Change>>();
+  ordered.sort_by_key(|change| std::cmp::Reverse((change.line, change.column)));
+  for change in ordered {
+    let line = &mut lines[change.line - 1];
+    let start = line
+      .char_indices()
+      .nth(change.column - 1)
+      .map_or(line.len(), |(i, _)| i);
+    let old = format!("{}:{}", change.fix.key, change.fix.from);
+    let new = format!("{}:{}", change.fix.key, change.fix.to);
+    line.replace_range(start..start + old.len(), &new);
+  }
+
+  (lines, changes)
+}
+
+/// Like `fix_batch`, for a whole batch file. The fixed file keeps the line endings of the
+/// original, `\r\n` if its first line ends with one and `\n` otherwise.
+pub fn fix_text(schema: &Schema, text: &str) -> (String, Vec<Change>) {
+  let ending = match text.find('\n') {
+    Some(i) if text[..i].ends_with('\r') => "\r\n",
+    _ => "\n",
+  };
+  let lines = text
+    .lines()
+    .map(|line| line.to_string())
+    .collect::<Vec<_>>();
+  let (fixed, changes) = fix_batch(schema, &lines);
+
+  let mut fixed = fixed.join(ending);
+  if text.ends_with('\n') {
+    fixed.push_str(ending);
+  }
+  (fixed, changes)
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn fixes(text: &str) -> Vec<(String, String, &'static str)> {
+    let passport = text.parse::<Passport>().unwrap();
+    suggest(&Schema::puzzle(), &passport)
+      .into_iter()
+      .map(|fix| (fix.key, fix.to, fix.reason))
+      .collect()
+  }
+
+  #[test]
+  fn it_measures_edit_distance() {
+    assert_eq!(edit_distance("grn", "grn"), 0);
+    assert_eq!(edit_distance("gren", "grn"), 1);
+    assert_eq!(edit_distance("brm", "brn"), 1);
+    assert_eq!(edit_distance("", "abc"), 3);
+    assert_eq!(edit_distance("kitten", "sitting"), 3);
+  }
+
+  #[test]
+  fn it_suggests_fixes_it_can_apply_safely() {
+    assert_eq!(
+      fixes("hgt:70 hcl:#ABC123 pid:12345 ecl:hzk"),
+      vec![
+        (
+          "hgt".to_string(),
+          "70in".to_string(),
+          "added the only unit that fits"
+        ),
+        ("hcl".to_string(), "#abc123".to_string(), "lower-cased"),
+        (
+          "ecl".to_string(),
+          "hzl".to_string(),
+          "closest allowed value"
+        ),
+        ("pid".to_string(), "000012345".to_string(), "zero-padded"),
+      ]
+    );
+    assert_eq!(
+      fixes("ecl:BLU"),
+      vec![("ecl".to_string(), "blu".to_string(), "lower-cased")]
+    );
+  }
+
+  #[test]
+  fn it_does_not_guess() {
+    // 100 fits neither unit, "grr" is one edit from both "grn" and "gry", and a pid that is
+    // too long can not be padded
+    assert!(fixes("hgt:100 ecl:grr pid:1234567890 byr:1900").is_empty());
+    assert!(fixes("hgt:170cm ecl:grn").is_empty());
+  }
+
+  #[test]
+  fn it_fixes_a_batch_in_place() {
+    let lines = ["hgt:70 ecl:BRN", "pid:1", "", "bad token:x:y", "", "hgt:70"]
+      .iter()
+      .map(|line| line.to_string())
+      .collect::<Vec<String>>();
+    let (fixed, changes) = fix_batch(&Schema::puzzle(), &lines);
+
+    assert_eq!(
+      fixed,
+      vec![
+        "hgt:70in ecl:brn",
+        "pid:000000001",
+        "",
+        "bad token:x:y",
+        "",
+        "hgt:70in"
+      ]
+    );
+    assert_eq!(
+      changes
+        .iter()
+        .map(|c| (c.line, c.column, c.fix.key.as_str()))
+        .collect::<Vec<_>>(),
+      vec![(1, 1, "hgt"), (1, 8, "ecl"), (2, 1, "pid"), (6, 1, "hgt")]
+    );
+  }
+
+  #[test]
+  fn it_keeps_the_line_endings_of_a_batch() {
+    let schema = Schema::puzzle();
+
+    let (fixed, changes) = fix_text(&schema, "hgt:70\r\necl:BRN\r\n\r\npid:1\r\n");
+    assert_eq!(fixed, "hgt:70in\r\necl:brn\r\n\r\npid:000000001\r\n");
+    assert_eq!(changes.len(), 3);
+
+    assert_eq!(
+      fix_text(&schema, "hgt:70\n\npid:1").0,
+      "hgt:70in\n\npid:000000001"
+    );
+  }
+}
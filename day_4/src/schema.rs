@@ -0,0 +1,486 @@
+use std::collections::{BTreeMap, HashSet};
+use std::fmt;
+use std::fs;
+use std::path::Path;
+
+use regex::Regex;
+use serde::{de, Deserialize, Deserializer};
+
+use crate::diagnostics::Reason;
+use crate::passport::Passport;
+
+/// What a field's value has to look like.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(tag = "rule", rename_all = "kebab-case")]
+pub enum Rule {
+  /// A four digit year between `min` and `max`, inclusive.
+  Year { min: u32, max: u32 },
+  /// A number directly followed by one of the units, within that unit's inclusive range.
+  Units { units: BTreeMap<String, (u32, u32)> },
+  /// The whole value matches the pattern, which should be anchored with `^` and `$`.
+  Regex {
+    #[serde(deserialize_with = "deserialize_regex")]
+    pattern: Regex,
+  },
+  /// The value is one of the listed values.
+  OneOf { values: Vec<String> },
+  /// Any value is accepted, only the field's presence is checked.
+  Any,
+}
+
+fn deserialize_regex<'de, D>(deserializer: D) -> Result<Regex, D::Error>
+where
+  D: Deserializer<'de>,
+{
+  let pattern = String::deserialize(deserializer)?;
+  Regex::new(&pattern).map_err(de::Error::custom)
+}
+
+/// Parses a plain run of ASCII digits, rejecting signs and whitespace that `parse` would
+/// otherwise accept or trip over.
+fn parse_digits(value: &str) -> Option<u32> {
+  if !value.is_empty() && value.bytes().all(|b| b.is_ascii_digit()) {
+    value.parse().ok()
+  } else {
+    None
+  }
+}
+
+/// One reason a passport breaks a schema.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Violation {
+  Missing {
+    key: String,
+  },
+  UnknownKey {
+    key: String,
+  },
+  NotANumber {
+    key: String,
+    value: String,
+  },
+  NotAYear {
+    key: String,
+    value: String,
+  },
+  OutOfRange {
+    key: String,
+    value: u32,
+    unit: String,
+    min: u32,
+    max: u32,
+  },
+  BadUnit {
+    key: String,
+    value: String,
+    units: Vec<String>,
+  },
+  PatternMismatch {
+    key: String,
+    value: String,
+    pattern: String,
+  },
+  NotAllowed {
+    key: String,
+    value: String,
+    values: Vec<String>,
+  },
+}
+
+impl Violation {
+  pub fn key(&self) -> &str {
+    match self {
+      Violation::Missing { key }
+      | Violation::UnknownKey { key }
+      | Violation::NotANumber { key, .. }
+      | Violation::NotAYear { key, .. }
+      | Violation::OutOfRange { key, .. }
+      | Violation::BadUnit { key, .. }
+      | Violation::PatternMismatch { key, .. }
+      | Violation::NotAllowed { key, .. } => key,
+    }
+  }
+
+  /// Whether the violation makes a passport invalid. Unknown keys are only reported, so that
+  /// a schema does not have to list every field a batch may contain.
+  pub fn invalidates(&self) -> bool {
+    !matches!(self, Violation::UnknownKey { .. })
+  }
+
+  pub fn reason(&self) -> Reason {
+    match self {
+      Violation::Missing { .. } => Reason::Missing,
+      Violation::UnknownKey { .. } => Reason::UnknownKey,
+      Violation::NotANumber { .. } => Reason::NotANumber,
+      Violation::NotAYear { .. } => Reason::NotAYear,
+      Violation::OutOfRange { .. } => Reason::OutOfRange,
+      Violation::BadUnit { .. } => Reason::BadUnit,
+      Violation::PatternMismatch { .. } => Reason::PatternMismatch,
+      Violation::NotAllowed { .. } => Reason::NotAllowed,
+    }
+  }
+}
+
+impl fmt::Display for Violation {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    match self {
+      Violation::Missing { key } => write!(f, "{}: missing", key),
+      Violation::UnknownKey { key } => write!(f, "{}: unknown key", key),
+      Violation::NotANumber { key, value } => write!(f, "{}: {:?} is not a number", key, value),
+      Violation::NotAYear { key, value } => {
+        write!(f, "{}: {:?} is not a four digit year", key, value)
+      }
+      Violation::OutOfRange {
+        key,
+        value,
+        unit,
+        min,
+        max,
+      } => write!(
+        f,
+        "{}: {}{} is not in {}{}..={}{}",
+        key, value, unit, min, unit, max, unit
+      ),
+      Violation::BadUnit { key, value, units } => write!(
+        f,
+        "{}: {:?} does not end in a unit, expected {}",
+        key,
+        value,
+        units.join(" or ")
+      ),
+      Violation::PatternMismatch {
+        key,
+        value,
+        pattern,
+      } => write!(f, "{}: {:?} does not match /{}/", key, value, pattern),
+      Violation::NotAllowed { key, value, values } => write!(
+        f,
+        "{}: {:?} is not one of {}",
+        key,
+        value,
+        values.join(", ")
+      ),
+    }
+  }
+}
+
+impl Rule {
+  pub fn check(&self, key: &str, value: &str) -> Result<(), Violation> {
+    let key = key.to_string();
+    let in_range = |number: u32, unit: &str, min: u32, max: u32| {
+      if (min..=max).contains(&number) {
+        Ok(())
+      } else {
+        Err(Violation::OutOfRange {
+          key: key.clone(),
+          value: number,
+          unit: unit.to_string(),
+          min,
+          max,
+        })
+      }
+    };
+
+    match self {
+      Rule::Year { min, max } => match parse_digits(value) {
+        Some(year) if value.len() == 4 => in_range(year, "", *min, *max),
+        Some(_) => Err(Violation::NotAYear {
+          key,
+          value: value.to_string(),
+        }),
+        None => Err(Violation::NotANumber {
+          key,
+          value: value.to_string(),
+        }),
+      },
+      Rule::Units { units } => {
+        // The longest matching unit wins, so "cm" is not read as a number followed by "m"
+        let unit = units
+          .iter()
+          .filter(|(unit, _)| value.ends_with(unit.as_str()))
+          .max_by_key(|(unit, _)| unit.len());
+
+        match unit {
+          Some((unit, (min, max))) => {
+            let number = &value[..value.len() - unit.len()];
+            match parse_digits(number) {
+              Some(number) => in_range(number, unit, *min, *max),
+              None => Err(Violation::NotANumber {
+                key,
+                value: value.to_string(),
+              }),
+            }
+          }
+          None => Err(Violation::BadUnit {
+            key,
+            value: value.to_string(),
+            units: units.keys().cloned().collect(),
+          }),
+        }
+      }
+      Rule::Regex { pattern } if pattern.is_match(value) => Ok(()),
+      Rule::Regex { pattern } => Err(Violation::PatternMismatch {
+        key,
+        value: value.to_string(),
+        pattern: pattern.to_string(),
+      }),
+      Rule::OneOf { values } if values.iter().any(|v| v == value) => Ok(()),
+      Rule::OneOf { values } => Err(Violation::NotAllowed {
+        key,
+        value: value.to_string(),
+        values: values.clone(),
+      }),
+      Rule::Any => Ok(()),
+    }
+  }
+
+  pub fn matches(&self, value: &str) -> bool {
+    self.check("", value).is_ok()
+  }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct FieldRule {
+  pub key: String,
+  #[serde(default)]
+  pub optional: bool,
+  #[serde(flatten)]
+  pub rule: Rule,
+}
+
+/// A set of passport field rules, loaded from a TOML file such as:
+///
+/// ```toml
+/// [[field]]
+/// key = "byr"
+/// rule = "year"
+/// min = 1920
+/// max = 2002
+///
+/// [[field]]
+/// key = "cid"
+/// rule = "any"
+/// optional = true
+/// ```
+///
+/// or the same structure as JSON, `{ "field": [{ "key": "byr", "rule": "year", ... }] }`.
+#[derive(Debug, Clone, Deserialize)]
+pub struct Schema {
+  #[serde(rename = "field")]
+  pub fields: Vec<FieldRule>,
+}
+
+impl Schema {
+  /// The rules from the puzzle, as declared in `rules/2020.toml`.
+  pub fn puzzle() -> Schema {
+    Schema::from_toml(include_str!("../rules/2020.toml")).expect("the 2020 rules are valid")
+  }
+
+  /// Loads a JSON schema from a `.json` file and a TOML schema from anything else.
+  pub fn load(filename: &str) -> Result<Schema, String> {
+    let contents = fs::read_to_string(filename)
+      .map_err(|err| format!("could not read {}: {}", filename, err))?;
+
+    let schema = match Path::new(filename).extension() {
+      Some(extension) if extension == "json" => Schema::from_json(&contents),
+      _ => Schema::from_toml(&contents),
+    };
+    schema.map_err(|err| format!("{}: {}", filename, err))
+  }
+
+  pub fn from_toml(contents: &str) -> Result<Schema, String> {
+    toml::from_str::<Schema>(contents)
+      .map_err(|err| err.to_string())?
+      .checked()
+  }
+
+  pub fn from_json(contents: &str) -> Result<Schema, String> {
+    serde_json::from_str::<Schema>(contents)
+      .map_err(|err| err.to_string())?
+      .checked()
+  }
+
+  fn checked(self) -> Result<Schema, String> {
+    let mut keys = HashSet::new();
+    for field in self.fields.iter() {
+      if !keys.insert(field.key.as_str()) {
+        return Err(format!("field {:?} is declared more than once", field.key));
+      }
+    }
+
+    Ok(self)
+  }
+
+  /// Whether every required field is present, regardless of its value.
+  pub fn has_required_fields(&self, passport: &Passport) -> bool {
+    self
+      .fields
+      .iter()
+      .all(|field| field.optional || passport.contains_key(&field.key))
+  }
+
+  /// Every violation in a passport: missing required fields and invalid values in schema
+  /// order, followed by keys the schema does not know in key order.
+  pub fn check(&self, passport: &Passport) -> Vec<Violation> {
+    let mut violations = self
+      .fields
+      .iter()
+      .filter_map(|field| match passport.get(&field.key) {
+        Some(value) => field.rule.check(&field.key, value).err(),
+        None if field.optional => None,
+        None => Some(Violation::Missing {
+          key: field.key.to_string(),
+        }),
+      })
+      .collect::<Vec<Violation>>();
+
+    let unknown = passport
+      .keys()
+      .filter(|key| self.fields.iter().all(|field| field.key != *key));
+    violations.extend(unknown.map(|key| Violation::UnknownKey {
+      key: key.to_string(),
+    }));
+
+    violations
+  }
+
+  /// Whether every required field is present and every known field present has a valid
+  /// value.
+  pub fn is_valid(&self, passport: &Passport) -> bool {
+    self.check(passport).iter().all(|v| !v.invalidates())
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn passport(text: &str) -> Passport {
+    text.parse().unwrap()
+  }
+
+  #[test]
+  fn it_matches_each_kind_of_rule() {
+    let schema = Schema::puzzle();
+    let rule = |key: &str| {
+      &schema
+        .fields
+        .iter()
+        .find(|field| field.key == key)
+        .unwrap()
+        .rule
+    };
+
+    assert!(rule("byr").matches("2002"));
+    assert!(!rule("byr").matches("2003"));
+    assert!(!rule("byr").matches("02002"));
+    assert!(!rule("byr").matches("+200"));
+    assert!(rule("hgt").matches("60in"));
+    assert!(rule("hgt").matches("190cm"));
+    assert!(!rule("hgt").matches("190in"));
+    assert!(!rule("hgt").matches("190"));
+    assert!(!rule("hgt").matches("cm"));
+    assert!(rule("hcl").matches("#123abc"));
+    assert!(!rule("hcl").matches("#123abz"));
+    assert!(rule("ecl").matches("brn"));
+    assert!(!rule("ecl").matches("wat"));
+    assert!(rule("pid").matches("000000001"));
+    assert!(!rule("pid").matches("0123456789"));
+    assert!(rule("cid").matches("anything"));
+  }
+
+  #[test]
+  fn it_explains_violations() {
+    let schema = Schema::puzzle();
+    let check = |key: &str, value: &str| {
+      let rule = &schema
+        .fields
+        .iter()
+        .find(|field| field.key == key)
+        .unwrap()
+        .rule;
+      rule.check(key, value).unwrap_err().to_string()
+    };
+
+    assert_eq!(check("byr", "19x0"), "byr: \"19x0\" is not a number");
+    assert_eq!(
+      check("byr", "02002"),
+      "byr: \"02002\" is not a four digit year"
+    );
+    assert_eq!(check("iyr", "2021"), "iyr: 2021 is not in 2010..=2020");
+    assert_eq!(check("hgt", "200cm"), "hgt: 200cm is not in 150cm..=193cm");
+    assert_eq!(check("hgt", "cm"), "hgt: \"cm\" is not a number");
+    assert_eq!(
+      check("hgt", "70"),
+      "hgt: \"70\" does not end in a unit, expected cm or in"
+    );
+    assert_eq!(
+      check("pid", "12345"),
+      "pid: \"12345\" does not match /^[0-9]{9}$/"
+    );
+    assert_eq!(
+      check("ecl", "red"),
+      "ecl: \"red\" is not one of amb, blu, brn, gry, grn, hzl, oth"
+    );
+  }
+
+  #[test]
+  fn it_collects_every_violation() {
+    let schema = Schema::puzzle();
+    let violations = schema.check(&passport(
+      "byr:1800 hgt:70 hcl:#123abc ecl:brn pid:000000001 zzz:1",
+    ));
+
+    assert_eq!(
+      violations
+        .iter()
+        .map(|v| (v.key(), v.reason()))
+        .collect::<Vec<_>>(),
+      vec![
+        ("byr", Reason::OutOfRange),
+        ("iyr", Reason::Missing),
+        ("eyr", Reason::Missing),
+        ("hgt", Reason::BadUnit),
+        ("zzz", Reason::UnknownKey),
+      ]
+    );
+    assert!(!violations[4].invalidates());
+    assert!(schema.is_valid(&passport(
+      "byr:1937 iyr:2017 eyr:2020 hgt:183cm hcl:#fffffd ecl:gry pid:860033327 zzz:1"
+    )));
+  }
+
+  #[test]
+  fn it_loads_json_schemas() {
+    let schema = Schema::from_json(
+      r#"{ "field": [
+        { "key": "byr", "rule": "year", "min": 1900, "max": 1950 },
+        { "key": "hgt", "rule": "units", "units": { "m": [1, 2] }, "optional": true }
+      ] }"#,
+    )
+    .unwrap();
+
+    assert!(schema.is_valid(&passport("byr:1901")));
+    assert!(schema.is_valid(&passport("byr:1901 hgt:2m")));
+    assert!(schema.is_valid(&passport("byr:1901 cid:147")));
+    assert!(!schema.is_valid(&passport("byr:1901 hgt:3m")));
+    assert!(!schema.is_valid(&passport("byr:2000")));
+    assert!(!schema.has_required_fields(&passport("hgt:2m")));
+  }
+
+  #[test]
+  fn it_rejects_invalid_schemas() {
+    assert!(Schema::from_toml("[[field]]\nkey = \"byr\"\nrule = \"sparkle\"").is_err());
+    assert!(
+      Schema::from_toml("[[field]]\nkey = \"hcl\"\nrule = \"regex\"\npattern = \"(\"").is_err()
+    );
+    assert!(Schema::from_toml("[[field]]\nkey = \"byr\"\nrule = \"year\"\nmin = 1").is_err());
+
+    let duplicate =
+      "[[field]]\nkey = \"byr\"\nrule = \"any\"\n\n[[field]]\nkey = \"byr\"\nrule = \"any\"";
+    assert_eq!(
+      Schema::from_toml(duplicate).unwrap_err(),
+      "field \"byr\" is declared more than once"
+    );
+  }
+}
@@ -0,0 +1,133 @@
+use shared::{InputError, Solver};
+
+/// Decodes a boarding pass such as `FBFBBFFRLR` into its row and column. The first seven
+/// characters halve the rows (`F` front, `B` back), the last three the columns (`L`, `R`).
+pub fn find_seat_details(row_encoding: &str) -> Result<(usize, usize), String> {
+  let chars = row_encoding.chars().collect::<Vec<char>>();
+  if chars.len() != 10 {
+    return Err(format!("expected 10 characters, got {}", chars.len()));
+  }
+
+  let mut start_row = 0;
+  let mut end_row = 127;
+  let mut start_col = 0;
+  let mut end_col = 7;
+
+  for (i, chr) in chars.into_iter().enumerate() {
+    let half_row = (end_row - start_row) / 2;
+    let half_col = (end_col - start_col) / 2;
+    match (i < 7, chr) {
+      (true, 'F') => end_row = end_row - half_row - 1,
+      (true, 'B') => start_row = start_row + half_row + 1,
+      (false, 'L') => end_col = end_col - half_col - 1,
+      (false, 'R') => start_col = start_col + half_col + 1,
+      (true, other) => {
+        return Err(format!(
+          "unknown row {:?} at column {}, expected 'F' or 'B'",
+          other,
+          i + 1
+        ))
+      }
+      (false, other) => {
+        return Err(format!(
+          "unknown seat {:?} at column {}, expected 'L' or 'R'",
+          other,
+          i + 1
+        ))
+      }
+    }
+  }
+
+  Ok((start_row, start_col))
+}
+
+pub fn calculate_seat_id((row, col): (usize, usize)) -> usize {
+  row * 8 + col
+}
+
+pub fn find_available_seat_id(sorted_seat_ids: &[usize]) -> Option<usize> {
+  sorted_seat_ids
+    .windows(2)
+    .find(|pair| pair[0] + 1 != pair[1])
+    .map(|pair| pair[0] + 1)
+}
+
+pub struct Day5;
+
+impl Solver for Day5 {
+  type Input = Vec<usize>;
+  type Answer = usize;
+
+  fn day(&self) -> u8 {
+    5
+  }
+
+  fn input_dir(&self) -> &'static str {
+    env!("CARGO_MANIFEST_DIR")
+  }
+
+  fn parse(&self, boarding_passes: &[String]) -> Result<Vec<usize>, InputError> {
+    let mut seat_ids = boarding_passes
+      .iter()
+      .enumerate()
+      .map(|(i, pass)| {
+        let seat_details =
+          find_seat_details(pass).map_err(|err| InputError::parse(i + 1, pass, err))?;
+
+        Ok(calculate_seat_id(seat_details))
+      })
+      .collect::<Result<Vec<usize>, InputError>>()?;
+
+    seat_ids.sort();
+
+    Ok(seat_ids)
+  }
+
+  fn part_one(&self, seat_ids: &Vec<usize>) -> Option<usize> {
+    seat_ids.last().copied()
+  }
+
+  fn part_two(&self, seat_ids: &Vec<usize>) -> Option<usize> {
+    find_available_seat_id(seat_ids)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn it_finds_seat_row() {
+    let (row, col) = find_seat_details("BFFFBBFRRR").unwrap();
+    assert_eq!(row, 70);
+    assert_eq!(col, 7);
+
+    let (row, col) = find_seat_details("FFFBBBFRRR").unwrap();
+    assert_eq!(row, 14);
+    assert_eq!(col, 7);
+
+    let (row, col) = find_seat_details("BBFFBBFRLL").unwrap();
+    assert_eq!(row, 102);
+    assert_eq!(col, 4);
+  }
+
+  #[test]
+  fn it_rejects_invalid_boarding_passes() {
+    assert!(find_seat_details("FBFZBBFRLR").is_err());
+    assert!(find_seat_details("FBFBBFFRLB").is_err());
+    assert!(find_seat_details("FBFBBFFRL").is_err());
+
+    let lines = vec!["FBFBBFFRLR".to_string(), "FBFZBBFRLR".to_string()];
+    assert_eq!(
+      Day5.parse(&lines).unwrap_err().to_string(),
+      "line 2: could not parse \"FBFZBBFRLR\": unknown row 'Z' at column 4, expected 'F' or 'B'"
+    );
+  }
+
+  #[test]
+  fn it_has_no_answers_for_empty_input() {
+    let seat_ids = Day5.parse(&[]).unwrap();
+    assert_eq!(Day5.part_one(&seat_ids), None);
+    assert_eq!(Day5.part_two(&seat_ids), None);
+  }
+}
@@ -0,0 +1,109 @@
+use std::collections::HashMap;
+use std::collections::HashSet;
+
+use shared::{InputError, Solver};
+
+pub fn count_any_yes_anwer(group_answers: &Vec<String>) -> usize {
+  let mut unique_answers = HashSet::new();
+
+  for person_answers in group_answers {
+    for answer in person_answers.chars() {
+      unique_answers.insert(answer);
+    }
+  }
+
+  unique_answers.len()
+}
+
+pub fn count_all_yes_anwer(group_answers: &Vec<String>) -> usize {
+  let mut answer_count = HashMap::new();
+
+  for person_answers in group_answers {
+    for answer in person_answers.chars() {
+      match answer_count.get_mut(&answer) {
+        Some(count) => {
+          *count += 1;
+        }
+        _ => {
+          answer_count.insert(answer, 1);
+        }
+      }
+    }
+  }
+
+  let num_participants = group_answers.len();
+  answer_count
+    .iter()
+    .filter(|(_key, value)| **value == num_participants)
+    .count()
+}
+
+pub fn parse_groups_from_file(filename: &str) -> Result<Vec<Vec<String>>, InputError> {
+  shared::read_records(filename)
+}
+
+pub struct Day6;
+
+impl Solver for Day6 {
+  type Input = Vec<Vec<String>>;
+  type Answer = usize;
+
+  fn day(&self) -> u8 {
+    6
+  }
+
+  fn input_dir(&self) -> &'static str {
+    env!("CARGO_MANIFEST_DIR")
+  }
+
+  fn parse(&self, lines: &[String]) -> Result<Vec<Vec<String>>, InputError> {
+    Ok(shared::parse_records(lines))
+  }
+
+  fn part_one(&self, groups: &Vec<Vec<String>>) -> Option<usize> {
+    Some(groups.iter().map(count_any_yes_anwer).sum())
+  }
+
+  fn part_two(&self, groups: &Vec<Vec<String>>) -> Option<usize> {
+    Some(groups.iter().map(count_all_yes_anwer).sum())
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  #[test]
+  fn it_parses_groups_from_input() {
+    let groups = parse_groups_from_file("test_input.txt").unwrap();
+    assert_eq!(groups.len(), 5, "num groups");
+    assert_eq!(groups[0], vec!["abc"], "single person answers");
+    assert_eq!(groups[2], vec!["ab", "ac"], "multiple people answers");
+  }
+
+  #[test]
+  fn it_counts_group_answers() {
+    let groups = parse_groups_from_file("test_input.txt").unwrap();
+    let answers = [3, 3, 3, 1, 1];
+
+    for i in 0..groups.len() {
+      assert_eq!(
+        count_any_yes_anwer(&groups[i]),
+        answers[i],
+        "correct answer count"
+      )
+    }
+  }
+
+  #[test]
+  fn it_counts_all_yes_answers() {
+    let groups = parse_groups_from_file("test_input.txt").unwrap();
+
+    let answer_counts = groups
+      .iter()
+      .map(count_all_yes_anwer)
+      .collect::<Vec<usize>>();
+
+    let answer_sum: usize = answer_counts.iter().sum();
+    assert_eq!(answer_sum, 6, "num all yes answers");
+  }
+}
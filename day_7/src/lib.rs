@@ -0,0 +1,254 @@
+use std::collections::{HashMap, HashSet};
+
+use shared::{InputError, Solver};
+
+#[derive(Debug)]
+pub struct Bag {
+    pub color: String,
+    pub contents: Vec<BagContent>,
+}
+#[derive(Debug)]
+pub struct BagContent {
+    pub color: String,
+    pub amount: usize,
+}
+
+impl Bag {
+    pub fn parse_luggage_rule(rule: &str) -> Result<Bag, String> {
+        let splt = rule.split(" contain ").collect::<Vec<&str>>();
+        if splt.len() != 2 {
+            return Err("expected '<color> bags contain <contents>'".to_string());
+        }
+        let bag_color = splt[0].replace(" bags", "");
+        if bag_color.trim().is_empty() {
+            return Err("missing the color of the bag".to_string());
+        }
+
+        let contains_strs = splt[1]
+            .split(",")
+            .map(|text| {
+                text.replace("bags", "")
+                    .replace("bag", "")
+                    .replace(".", "")
+                    .trim()
+                    .to_string()
+            })
+            .collect::<Vec<String>>();
+
+        if contains_strs.len() == 1 && contains_strs[0] == "no other" {
+            return Ok(Bag {
+                color: bag_color.to_string(),
+                contents: vec![],
+            });
+        }
+
+        let mut bag_contents = vec![];
+        for content in contains_strs.iter() {
+            let tokens = content.split(" ").collect::<Vec<&str>>();
+
+            let amount = tokens[0]
+                .parse::<usize>()
+                .map_err(|err| format!("invalid amount {:?}: {}", tokens[0], err))?;
+            let color = tokens[1..tokens.len()].join(" ");
+            if color.is_empty() {
+                return Err(format!("missing the color of {:?}", content));
+            }
+
+            bag_contents.push(BagContent { color, amount });
+        }
+
+        Ok(Bag {
+            color: bag_color.to_string(),
+            contents: bag_contents,
+        })
+    }
+
+    pub fn can_fit_color(&self, bag_rule_map: &HashMap<String, Bag>, color: &str) -> bool {
+        let mut can_fit = false;
+        // `parse_rules_to_map` rejects rules with undefined contents, so every bag is found
+        for current_bag in self
+            .contents
+            .iter()
+            .filter_map(|c| bag_rule_map.get(&c.color))
+        {
+            if current_bag.color == color {
+                return true;
+            }
+
+            let child_can_fit = current_bag.can_fit_color(bag_rule_map, color);
+
+            if child_can_fit {
+                can_fit = true;
+            }
+        }
+
+        can_fit
+    }
+
+    fn count_num_bags_inside(&self, bag_rule_map: &HashMap<String, Bag>) -> usize {
+        let mut count = 1;
+        for bag_content in &self.contents {
+            if let Some(current_bag) = bag_rule_map.get(&bag_content.color) {
+                count += bag_content.amount * current_bag.count_num_bags_inside(bag_rule_map);
+            }
+        }
+
+        count
+    }
+
+    pub fn count_total_num_bags(&self, bag_rule_map: &HashMap<String, Bag>) -> usize {
+        // subtract 1 for the initial bag which does not count
+        &self.count_num_bags_inside(bag_rule_map) - 1
+    }
+}
+
+/// Parses every rule, rejecting rules that contain bags no rule defines.
+pub fn parse_rules_to_map(rules: &Vec<&str>) -> Result<HashMap<String, Bag>, InputError> {
+    let bags = rules
+        .iter()
+        .enumerate()
+        .map(|(i, rule)| {
+            Bag::parse_luggage_rule(rule).map_err(|err| InputError::parse(i + 1, rule, err))
+        })
+        .collect::<Result<Vec<Bag>, InputError>>()?;
+
+    let defined = bags
+        .iter()
+        .map(|bag| bag.color.as_str())
+        .collect::<HashSet<&str>>();
+    for (i, bag) in bags.iter().enumerate() {
+        let undefined = bag
+            .contents
+            .iter()
+            .find(|content| !defined.contains(content.color.as_str()));
+
+        if let Some(content) = undefined {
+            return Err(InputError::parse(
+                i + 1,
+                rules[i],
+                format!("{} bags are never defined", content.color),
+            ));
+        }
+    }
+
+    let mut bag_rule_map: HashMap<String, Bag> = HashMap::new();
+    for bag in bags {
+        let color = bag.color.clone();
+        bag_rule_map.insert(color, bag);
+    }
+
+    Ok(bag_rule_map)
+}
+
+pub fn count_bags_that_can_fit_color(
+    bag_rule_map: &HashMap<String, Bag>,
+    bag_color: &str,
+) -> usize {
+    let mut count = 0;
+    for (_key, bag) in bag_rule_map.iter() {
+        if bag.can_fit_color(bag_rule_map, bag_color) {
+            count += 1;
+        }
+    }
+
+    count
+}
+
+pub struct Day7;
+
+impl Solver for Day7 {
+    type Input = HashMap<String, Bag>;
+    type Answer = usize;
+
+    fn day(&self) -> u8 {
+        7
+    }
+
+    fn input_dir(&self) -> &'static str {
+        env!("CARGO_MANIFEST_DIR")
+    }
+
+    fn parse(&self, lines: &[String]) -> Result<HashMap<String, Bag>, InputError> {
+        let rules = lines.iter().map(|s| &**s).collect::<Vec<&str>>();
+
+        parse_rules_to_map(&rules)
+    }
+
+    fn part_one(&self, rules_map: &HashMap<String, Bag>) -> Option<usize> {
+        Some(count_bags_that_can_fit_color(rules_map, "shiny gold"))
+    }
+
+    fn part_two(&self, rules_map: &HashMap<String, Bag>) -> Option<usize> {
+        rules_map
+            .get("shiny gold")
+            .map(|bag| bag.count_total_num_bags(rules_map))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_parses_luggage_rules() {
+        let bag = Bag::parse_luggage_rule(
+            "light red bags contain 1 bright white bag, 2 muted yellow bags.",
+        )
+        .unwrap();
+        assert_eq!(bag.color, "light red");
+        assert_eq!(bag.contents.len(), 2);
+        assert_eq!(bag.contents[0].color, "bright white");
+        assert_eq!(bag.contents[0].amount, 1);
+        assert_eq!(bag.contents[1].color, "muted yellow");
+        assert_eq!(bag.contents[1].amount, 2);
+
+        let bag = Bag::parse_luggage_rule("bright white bags contain 1 shiny gold bag.").unwrap();
+        assert_eq!(bag.color, "bright white");
+        assert_eq!(bag.contents.len(), 1);
+        assert_eq!(bag.contents[0].color, "shiny gold");
+        assert_eq!(bag.contents[0].amount, 1);
+
+        let bag = Bag::parse_luggage_rule("faded blue bags contain no other bags.").unwrap();
+        assert_eq!(bag.color, "faded blue");
+        assert_eq!(bag.contents.len(), 0);
+    }
+
+    #[test]
+    fn it_counts_bags_that_can_fit_a_color() {
+        let lines = shared::read_file("test_input.txt");
+
+        let rules = lines.iter().map(|s| &**s).collect::<Vec<&str>>();
+        let rules_map = parse_rules_to_map(&rules).unwrap();
+        assert_eq!(
+            count_bags_that_can_fit_color(&rules_map, "shiny gold"),
+            4,
+            "counts all bags that can contain a shiny gold bag"
+        );
+    }
+
+    #[test]
+    fn it_counts_bags_inside_a_bag() {
+        let lines = shared::read_file("test_2_input.txt");
+
+        let rules = lines.iter().map(|s| &**s).collect::<Vec<&str>>();
+        let rules_map = parse_rules_to_map(&rules).unwrap();
+
+        let bag = rules_map.get("shiny gold").unwrap();
+        assert_eq!(bag.count_total_num_bags(&rules_map), 126);
+    }
+
+    #[test]
+    fn it_rejects_invalid_rules() {
+        assert!(Bag::parse_luggage_rule("light red bags contain 1 bag.").is_err());
+        assert!(Bag::parse_luggage_rule(" bags contain no other bags.").is_err());
+
+        let rules = vec![
+            "dark blue bags contain no other bags.",
+            "light red bags contain 1 dark blue bag, 2 dark green bags.",
+        ];
+        assert_eq!(
+            parse_rules_to_map(&rules).unwrap_err().to_string(),
+            "line 2: could not parse \"light red bags contain 1 dark blue bag, 2 dark green bags.\": dark green bags are never defined"
+        );
+    }
+}
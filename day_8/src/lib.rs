@@ -0,0 +1,293 @@
+use std::collections::HashSet;
+
+use shared::{InputError, Solver};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Instruction {
+    Nop(isize),
+    Acc(isize),
+    Jmp(isize),
+}
+
+impl Instruction {
+    pub fn parse(line: &str) -> Result<Instruction, String> {
+        let data = line.split(' ').collect::<Vec<&str>>();
+        if data.len() != 2 {
+            return Err("expected '<operation> <argument>'".to_string());
+        }
+
+        let argument = data[1]
+            .parse::<isize>()
+            .map_err(|err| format!("invalid argument {:?}: {}", data[1], err))?;
+
+        match data[0] {
+            "nop" => Ok(Instruction::Nop(argument)),
+            "acc" => Ok(Instruction::Acc(argument)),
+            "jmp" => Ok(Instruction::Jmp(argument)),
+            instruct_id => Err(format!("invalid instruction {:?}", instruct_id)),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct TuringMachine<'a> {
+    pub instruction_counter: usize,
+    pub accumulator: isize,
+
+    pub execution_code: &'a [Instruction],
+}
+
+impl TuringMachine<'_> {
+    pub fn new(execution_code: &[Instruction]) -> TuringMachine<'_> {
+        TuringMachine {
+            instruction_counter: 0,
+            accumulator: 0,
+            execution_code,
+        }
+    }
+
+    /// Runs the current instruction. Returns `None`, leaving the machine as it is, when the
+    /// program has terminated or the instruction would jump before its start.
+    pub fn execute(&mut self, persist: bool) -> Option<(usize, isize)> {
+        let instruction = *self.execution_code.get(self.instruction_counter)?;
+
+        let mut instruction_counter = self.instruction_counter;
+        let mut accumulator = self.accumulator;
+
+        match instruction {
+            Instruction::Nop(_) => instruction_counter += 1,
+            Instruction::Acc(argument) => {
+                accumulator += argument;
+                instruction_counter += 1;
+            }
+            Instruction::Jmp(offset) => {
+                instruction_counter = instruction_counter.checked_add_signed(offset)?;
+            }
+        }
+
+        if persist {
+            self.instruction_counter = instruction_counter;
+            self.accumulator = accumulator;
+        }
+
+        Some((instruction_counter, accumulator))
+    }
+
+    pub fn is_terminated(&self) -> bool {
+        self.instruction_counter >= self.execution_code.len()
+    }
+
+    pub fn skip(&mut self) {
+        self.instruction_counter += 1;
+    }
+
+    pub fn reset(&mut self) {
+        self.instruction_counter = 0;
+        self.accumulator = 0;
+    }
+}
+
+/// Runs until an instruction is about to be executed a second time. Returns `false` when the
+/// program stops before that.
+pub fn execute_until_loop(tm: &mut TuringMachine) -> bool {
+    let mut set: HashSet<usize> = HashSet::new();
+
+    loop {
+        match set.get(&tm.instruction_counter) {
+            Some(_instruction) => return true,
+            None => {
+                set.insert(tm.instruction_counter);
+                if tm.execute(true).is_none() {
+                    return false;
+                }
+            }
+        }
+    }
+}
+
+/// Looks for the `nop` or `jmp` that, when skipped, lets the program run past its last
+/// instruction. Returns `false` when there is no such instruction.
+#[allow(unused_assignments)]
+pub fn find_terminating_sequence(tm: &mut TuringMachine) -> bool {
+    let mut set: HashSet<usize> = HashSet::new();
+    let mut skippable_instructions = vec![];
+
+    loop {
+        match set.get(&tm.instruction_counter) {
+            Some(_instruction) => break,
+            None => {
+                match tm.execution_code.get(tm.instruction_counter) {
+                    Some(Instruction::Nop(_)) | Some(Instruction::Jmp(_)) => {
+                        skippable_instructions.push(tm.instruction_counter)
+                    }
+                    _ => {}
+                }
+                set.insert(tm.instruction_counter);
+                if tm.execute(true).is_none() {
+                    break;
+                }
+            }
+        }
+    }
+
+    let mut is_loop = false;
+    for skippable in skippable_instructions {
+        tm.reset();
+        set = HashSet::new();
+        is_loop = false;
+        while !tm.is_terminated() {
+            match set.get(&tm.instruction_counter) {
+                Some(_instruction) => {
+                    is_loop = true;
+
+                    break;
+                }
+                None => {
+                    set.insert(tm.instruction_counter);
+                    if tm.instruction_counter == skippable {
+                        tm.skip();
+                    }
+
+                    if !tm.is_terminated() && tm.execute(true).is_none() {
+                        is_loop = true;
+
+                        break;
+                    }
+                }
+            }
+        }
+        if !is_loop {
+            return true;
+        }
+    }
+
+    false
+}
+
+pub struct Day8;
+
+impl Solver for Day8 {
+    type Input = Vec<Instruction>;
+    type Answer = isize;
+
+    fn day(&self) -> u8 {
+        8
+    }
+
+    fn input_dir(&self) -> &'static str {
+        env!("CARGO_MANIFEST_DIR")
+    }
+
+    fn parse(&self, lines: &[String]) -> Result<Vec<Instruction>, InputError> {
+        lines
+            .iter()
+            .enumerate()
+            .map(|(i, line)| {
+                Instruction::parse(line).map_err(|err| InputError::parse(i + 1, line, err))
+            })
+            .collect()
+    }
+
+    fn part_one(&self, instructions: &Vec<Instruction>) -> Option<isize> {
+        let mut tm = TuringMachine::new(instructions);
+        let looped = execute_until_loop(&mut tm);
+
+        looped.then_some(tm.accumulator)
+    }
+
+    fn part_two(&self, instructions: &Vec<Instruction>) -> Option<isize> {
+        let mut tm = TuringMachine::new(instructions);
+        let terminated = find_terminating_sequence(&mut tm);
+
+        terminated.then_some(tm.accumulator)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_executes_the_instructions() {
+        let instructions = Day8.parse(&shared::read_file("test_input.txt")).unwrap();
+
+        let mut tm = TuringMachine::new(&instructions);
+        assert_eq!(tm.instruction_counter, 0);
+        assert_eq!(tm.accumulator, 0);
+
+        tm.execute(true);
+        assert_eq!(tm.instruction_counter, 1);
+        assert_eq!(tm.accumulator, 0);
+
+        tm.execute(true);
+        assert_eq!(tm.instruction_counter, 2);
+        assert_eq!(tm.accumulator, 1);
+
+        tm.execute(true);
+        assert_eq!(tm.instruction_counter, 6);
+        assert_eq!(tm.accumulator, 1);
+
+        tm.execute(true);
+        assert_eq!(tm.instruction_counter, 7);
+        assert_eq!(tm.accumulator, 2);
+
+        tm.execute(true);
+        assert_eq!(tm.instruction_counter, 3);
+        assert_eq!(tm.accumulator, 2);
+
+        tm.execute(true);
+        assert_eq!(tm.instruction_counter, 4);
+        assert_eq!(tm.accumulator, 5);
+
+        tm.execute(true);
+        assert_eq!(tm.instruction_counter, 1);
+        assert_eq!(tm.accumulator, 5);
+    }
+
+    #[test]
+    fn it_executes_until_loop() {
+        let instructions = Day8.parse(&shared::read_file("test_input.txt")).unwrap();
+        let mut tm = TuringMachine::new(&instructions);
+
+        execute_until_loop(&mut tm);
+        assert_eq!(tm.instruction_counter, 1);
+        assert_eq!(tm.accumulator, 5);
+    }
+
+    #[test]
+    fn it_finds_terminating_sequence() {
+        let instructions = Day8.parse(&shared::read_file("test_input.txt")).unwrap();
+        let mut tm = TuringMachine::new(&instructions);
+
+        find_terminating_sequence(&mut tm);
+        assert_eq!(tm.accumulator, 8, "correct final accumulator state")
+    }
+
+    #[test]
+    fn it_rejects_invalid_instructions() {
+        for line in &["acc +x", "mul +3", "nop", "jmp +1 +2"] {
+            assert!(Instruction::parse(line).is_err(), "{}", line);
+        }
+
+        let lines = vec!["nop +0".to_string(), "acc +x".to_string()];
+        assert_eq!(
+            Day8.parse(&lines).unwrap_err().to_string(),
+            "line 2: could not parse \"acc +x\": invalid argument \"+x\": invalid digit found in string"
+        );
+    }
+
+    #[test]
+    fn it_stops_on_jumps_out_of_the_program() {
+        let instructions = vec![Instruction::Acc(1), Instruction::Jmp(-2)];
+        let mut tm = TuringMachine::new(&instructions);
+
+        assert!(!execute_until_loop(&mut tm));
+        assert_eq!(tm.instruction_counter, 1);
+        assert_eq!(Day8.part_one(&instructions), None);
+        assert_eq!(Day8.part_two(&instructions), Some(1));
+        assert_eq!(
+            Day8.part_two(&vec![Instruction::Jmp(0), Instruction::Jmp(-1)]),
+            None
+        );
+    }
+}
@@ -0,0 +1,149 @@
+use shared::{InputError, Solver};
+
+pub struct XMASCypher {
+  pub data: Vec<usize>,
+  pub preamble_length: usize,
+}
+
+impl XMASCypher {
+  pub fn parse_cypher(filename: &str, preamble_length: usize) -> Result<XMASCypher, InputError> {
+    let lines = shared::try_read_file(filename)?;
+
+    XMASCypher::from_lines(&lines, preamble_length)
+  }
+
+  pub fn from_lines(lines: &[String], preamble_length: usize) -> Result<XMASCypher, InputError> {
+    let data = shared::parse_lines::<usize>(lines)?;
+
+    Ok(XMASCypher {
+      data,
+      preamble_length,
+    })
+  }
+
+  fn has_pair_with_sum(&self, numbers: &[usize], desired_sum: usize) -> bool {
+    for i in 0..numbers.len() {
+      for j in 0..numbers.len() {
+        if numbers[i].checked_add(numbers[j]) == Some(desired_sum) && i != j {
+          return true;
+        }
+      }
+    }
+    false
+  }
+
+  /// The first number after the preamble that is not the sum of two of the numbers before
+  /// it, `None` when every number is.
+  pub fn find_invalid(&self) -> Option<usize> {
+    let mut current_index = self.preamble_length;
+
+    while current_index < self.data.len() {
+      let range_start = current_index - self.preamble_length;
+      let sum_range = &self.data[range_start..current_index];
+
+      if !self.has_pair_with_sum(sum_range, self.data[current_index]) {
+        return Some(self.data[current_index]);
+      }
+
+      current_index += 1;
+    }
+
+    None
+  }
+
+  /// The sum of the smallest and largest numbers of a contiguous run adding up to the
+  /// invalid number, `None` when there is no invalid number or no such run.
+  pub fn find_weakness(&self) -> Option<usize> {
+    let invalid_num = self.find_invalid()?;
+
+    let potential_sum_numbers = &self.data;
+
+    for i in 0..potential_sum_numbers.len() {
+      let mut current_sum = potential_sum_numbers[i];
+      let mut current_nums = vec![current_sum];
+      for num in potential_sum_numbers.iter().skip(i + 1) {
+        if current_sum < invalid_num {
+          current_sum = current_sum.saturating_add(*num);
+          current_nums.push(*num);
+        } else {
+          break;
+        }
+      }
+
+      // A run of a single number is the invalid number itself, not a weakness
+      if current_sum == invalid_num && current_nums.len() > 1 {
+        let min = current_nums.iter().min()?;
+        let max = current_nums.iter().max()?;
+
+        return min.checked_add(*max);
+      }
+    }
+
+    None
+  }
+}
+
+pub struct Day9;
+
+impl Solver for Day9 {
+  type Input = XMASCypher;
+  type Answer = usize;
+
+  fn day(&self) -> u8 {
+    9
+  }
+
+  fn input_dir(&self) -> &'static str {
+    env!("CARGO_MANIFEST_DIR")
+  }
+
+  fn parse(&self, lines: &[String]) -> Result<XMASCypher, InputError> {
+    XMASCypher::from_lines(lines, 25)
+  }
+
+  fn part_one(&self, cypher: &XMASCypher) -> Option<usize> {
+    cypher.find_invalid()
+  }
+
+  fn part_two(&self, cypher: &XMASCypher) -> Option<usize> {
+    cypher.find_weakness()
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn it_parses_input() {
+    let cypher = XMASCypher::parse_cypher("test_input.txt", 5).unwrap();
+    assert_eq!(cypher.data.len(), 20);
+    assert_eq!(cypher.data[0], 35);
+    assert_eq!(cypher.data[19], 576);
+    assert_eq!(cypher.preamble_length, 5);
+  }
+
+  #[test]
+  fn it_finds_the_invalid_number() {
+    let cypher = XMASCypher::parse_cypher("test_input.txt", 5).unwrap();
+    assert_eq!(cypher.find_invalid(), Some(127))
+  }
+
+  #[test]
+  fn it_finds_the_weakness() {
+    let cypher = XMASCypher::parse_cypher("test_input.txt", 5).unwrap();
+    assert_eq!(cypher.find_weakness(), Some(62))
+  }
+
+  #[test]
+  fn it_has_no_answers_without_an_invalid_number() {
+    let cypher = XMASCypher::parse_cypher("test_input.txt", 25).unwrap();
+    assert_eq!(Day9.part_one(&cypher), None);
+    assert_eq!(Day9.part_two(&cypher), None);
+
+    let lines = vec!["1".to_string(), "2".to_string(), "10".to_string()];
+    let cypher = XMASCypher::from_lines(&lines, 2).unwrap();
+    assert_eq!(cypher.find_invalid(), Some(10));
+    assert_eq!(cypher.find_weakness(), None);
+  }
+}
@@ -0,0 +1,107 @@
+use std::slice;
+
+/// The remaining command line arguments, handed to a binary's flag handler so that flags can
+/// take the value that follows them.
+pub struct Args<'a> {
+    args: slice::Iter<'a, String>,
+}
+
+impl<'a> Args<'a> {
+    /// The value following `flag`, which must be present. `what` describes it in the error,
+    /// as in "--output requires a path".
+    pub fn value(&mut self, flag: &str, what: &str) -> Result<&'a str, String> {
+        self.args
+            .next()
+            .map(|value| value.as_str())
+            .ok_or(format!("{} requires {}", flag, what))
+    }
+}
+
+/// Splits `args` into flags and positional arguments. Every argument starting with `--` is
+/// passed to `on_flag`, which takes any values it needs from the remaining arguments and
+/// returns `Ok(false)` for flags it does not know. Returns the positional arguments in order.
+pub fn parse_flags<'a>(
+    args: &'a [String],
+    mut on_flag: impl FnMut(&str, &mut Args<'a>) -> Result<bool, String>,
+) -> Result<Vec<&'a str>, String> {
+    let mut positionals = vec![];
+
+    let mut args = Args { args: args.iter() };
+    while let Some(arg) = args.args.next() {
+        if arg.starts_with("--") {
+            if !on_flag(arg, &mut args)? {
+                return Err(format!("unknown option {:?}", arg));
+            }
+        } else {
+            positionals.push(arg.as_str());
+        }
+    }
+
+    Ok(positionals)
+}
+
+/// Like `parse_flags`, for binaries whose only positional argument is an optional input path.
+pub fn parse_input_and_flags<'a>(
+    args: &'a [String],
+    on_flag: impl FnMut(&str, &mut Args<'a>) -> Result<bool, String>,
+) -> Result<Option<String>, String> {
+    match parse_flags(args, on_flag)?.as_slice() {
+        [] => Ok(None),
+        [input] => Ok(Some(input.to_string())),
+        [_, other, ..] => Err(format!("unexpected argument {:?}", other)),
+    }
+}
+
+/// Splits a command line on whitespace, for testing argument parsing.
+pub fn split_args(line: &str) -> Vec<String> {
+    line.split_whitespace().map(|a| a.to_string()).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_separates_flags_from_positional_arguments() {
+        let args = split_args("all --part 2 --verbose extra");
+        let mut part = None;
+        let mut verbose = false;
+
+        let positionals = parse_flags(&args, |flag, args| {
+            match flag {
+                "--part" => part = Some(args.value(flag, "a value")?),
+                "--verbose" => verbose = true,
+                _ => return Ok(false),
+            }
+            Ok(true)
+        })
+        .unwrap();
+
+        assert_eq!(positionals, vec!["all", "extra"]);
+        assert_eq!(part, Some("2"));
+        assert!(verbose);
+    }
+
+    #[test]
+    fn it_rejects_unknown_options_and_missing_values() {
+        let no_flags = |_: &str, _: &mut Args| Ok(false);
+        let with_value = |flag: &str, args: &mut Args| args.value(flag, "a path").map(|_| true);
+
+        assert_eq!(
+            parse_input_and_flags(&split_args("--nope"), no_flags),
+            Err("unknown option \"--nope\"".to_string())
+        );
+        assert_eq!(
+            parse_input_and_flags(&split_args("--output"), with_value),
+            Err("--output requires a path".to_string())
+        );
+        assert_eq!(
+            parse_input_and_flags(&split_args("a.txt b.txt"), no_flags),
+            Err("unexpected argument \"b.txt\"".to_string())
+        );
+        assert_eq!(
+            parse_input_and_flags(&split_args("a.txt"), no_flags),
+            Ok(Some("a.txt".to_string()))
+        );
+    }
+}
@@ -0,0 +1,460 @@
+use std::convert::TryFrom;
+use std::fmt;
+use std::ops::{Index, IndexMut};
+
+use crate::InputError;
+
+/// Offsets to the eight cells surrounding a position, as `(row, col)` deltas.
+pub const NEIGHBOUR_OFFSETS: [(isize, isize); 8] = [
+    (-1, -1),
+    (-1, 0),
+    (-1, 1),
+    (0, -1),
+    (0, 1),
+    (1, -1),
+    (1, 0),
+    (1, 1),
+];
+
+/// A character that does not map to any cell of a grid.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct UnknownCell(pub char);
+
+impl fmt::Display for UnknownCell {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "unknown cell {:?}", self.0)
+    }
+}
+
+/// A typed grid cell that is read from, and rendered as, a single character.
+pub trait Cell: Copy + TryFrom<char, Error = UnknownCell> {
+    fn to_char(self) -> char;
+}
+
+/// Which axes wrap around when addressing a grid with signed coordinates.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Wrapping {
+    None,
+    Horizontal,
+    Vertical,
+    Both,
+}
+
+/// A rectangular 2D grid stored row by row in a flat `Vec`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Grid<T> {
+    cells: Vec<T>,
+    width: usize,
+    height: usize,
+    wrapping: Wrapping,
+}
+
+impl<T> Grid<T> {
+    pub fn new(width: usize, height: usize, fill: T) -> Grid<T>
+    where
+        T: Clone,
+    {
+        Grid {
+            cells: vec![fill; width * height],
+            width,
+            height,
+            wrapping: Wrapping::None,
+        }
+    }
+
+    /// Builds a grid from row-major cells. Panics if `cells` is not a multiple of `width`.
+    pub fn from_cells(width: usize, cells: Vec<T>) -> Grid<T> {
+        assert!(
+            width > 0 && cells.len().is_multiple_of(width),
+            "{} cells can not be split into rows of {}",
+            cells.len(),
+            width
+        );
+
+        Grid {
+            height: cells.len() / width,
+            cells,
+            width,
+            wrapping: Wrapping::None,
+        }
+    }
+
+    /// Parses one row per line, mapping every character to a cell. All rows must have the same
+    /// length.
+    pub fn parse<F>(lines: &[String], mut to_cell: F) -> Result<Grid<T>, InputError>
+    where
+        F: FnMut(char) -> T,
+    {
+        Grid::try_parse(lines, |c| Ok(to_cell(c)))
+    }
+
+    /// Parses one row per line into typed cells, reporting the line and column of the first
+    /// character that is not a valid cell.
+    pub fn parse_cells(lines: &[String]) -> Result<Grid<T>, InputError>
+    where
+        T: Cell,
+    {
+        Grid::try_parse(lines, T::try_from)
+    }
+
+    fn try_parse<F>(lines: &[String], mut to_cell: F) -> Result<Grid<T>, InputError>
+    where
+        F: FnMut(char) -> Result<T, UnknownCell>,
+    {
+        let width = lines.first().map(|line| line.chars().count()).unwrap_or(0);
+        let mut cells = Vec::with_capacity(width * lines.len());
+
+        for (i, line) in lines.iter().enumerate() {
+            let row_width = line.chars().count();
+            if row_width != width {
+                return Err(InputError::parse(
+                    i + 1,
+                    line,
+                    format!("expected a row of {} cells, got {}", width, row_width),
+                ));
+            }
+
+            for (col, c) in line.chars().enumerate() {
+                let cell = to_cell(c).map_err(|err| {
+                    InputError::parse(i + 1, line, format!("{} at column {}", err, col + 1))
+                })?;
+                cells.push(cell);
+            }
+        }
+
+        Ok(Grid {
+            cells,
+            width,
+            height: lines.len(),
+            wrapping: Wrapping::None,
+        })
+    }
+
+    pub fn with_wrapping(mut self, wrapping: Wrapping) -> Grid<T> {
+        self.wrapping = wrapping;
+        self
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn wrapping(&self) -> Wrapping {
+        self.wrapping
+    }
+
+    pub fn get(&self, row: usize, col: usize) -> Option<&T> {
+        if row < self.height && col < self.width {
+            Some(&self.cells[row * self.width + col])
+        } else {
+            None
+        }
+    }
+
+    pub fn get_mut(&mut self, row: usize, col: usize) -> Option<&mut T> {
+        if row < self.height && col < self.width {
+            Some(&mut self.cells[row * self.width + col])
+        } else {
+            None
+        }
+    }
+
+    /// Resolves signed coordinates to a position, wrapping the axes selected by the grid's
+    /// `Wrapping` and rejecting anything else outside of the grid.
+    pub fn resolve(&self, row: isize, col: isize) -> Option<(usize, usize)> {
+        let wrap_rows = matches!(self.wrapping, Wrapping::Vertical | Wrapping::Both);
+        let wrap_cols = matches!(self.wrapping, Wrapping::Horizontal | Wrapping::Both);
+
+        let row = resolve_axis(row, self.height, wrap_rows)?;
+        let col = resolve_axis(col, self.width, wrap_cols)?;
+
+        Some((row, col))
+    }
+
+    pub fn get_signed(&self, row: isize, col: isize) -> Option<&T> {
+        self.resolve(row, col)
+            .and_then(|(row, col)| self.get(row, col))
+    }
+
+    /// The position `(d_row, d_col)` away from `(row, col)`, if it is addressable.
+    pub fn offset(
+        &self,
+        (row, col): (usize, usize),
+        (d_row, d_col): (isize, isize),
+    ) -> Option<(usize, usize)> {
+        self.resolve(row as isize + d_row, col as isize + d_col)
+    }
+
+    /// Cells at the given offsets from `(row, col)`, skipping offsets that leave the grid.
+    pub fn neighbours_at<'a>(
+        &'a self,
+        position: (usize, usize),
+        offsets: &'a [(isize, isize)],
+    ) -> impl Iterator<Item = ((usize, usize), &'a T)> + 'a {
+        offsets.iter().filter_map(move |offset| {
+            let (row, col) = self.offset(position, *offset)?;
+
+            Some(((row, col), &self[(row, col)]))
+        })
+    }
+
+    /// The (up to) eight cells surrounding `(row, col)`.
+    pub fn neighbours(
+        &self,
+        position: (usize, usize),
+    ) -> impl Iterator<Item = ((usize, usize), &T)> + '_ {
+        self.neighbours_at(position, &NEIGHBOUR_OFFSETS)
+    }
+
+    /// Panics if `row` is outside of the grid.
+    pub fn row(&self, row: usize) -> &[T] {
+        assert!(
+            row < self.height,
+            "row {} outside of {}x{} grid",
+            row,
+            self.width,
+            self.height
+        );
+
+        &self.cells[row * self.width..(row + 1) * self.width]
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> + '_ {
+        (0..self.height).map(move |row| self.row(row))
+    }
+
+    /// Panics if `col` is outside of the grid.
+    pub fn column(&self, col: usize) -> impl Iterator<Item = &T> + '_ {
+        assert!(
+            col < self.width,
+            "column {} outside of {}x{} grid",
+            col,
+            self.width,
+            self.height
+        );
+
+        self.cells.iter().skip(col).step_by(self.width)
+    }
+
+    pub fn columns(&self) -> impl Iterator<Item = impl Iterator<Item = &T> + '_> + '_ {
+        (0..self.width).map(move |col| self.column(col))
+    }
+
+    /// Every cell together with its `(row, col)` position, row by row.
+    pub fn iter(&self) -> impl Iterator<Item = ((usize, usize), &T)> + '_ {
+        self.cells
+            .iter()
+            .enumerate()
+            .map(move |(i, cell)| ((i / self.width, i % self.width), cell))
+    }
+
+    pub fn map<U, F>(&self, mut f: F) -> Grid<U>
+    where
+        F: FnMut((usize, usize), &T) -> U,
+    {
+        Grid {
+            cells: self
+                .iter()
+                .map(|(position, cell)| f(position, cell))
+                .collect(),
+            width: self.width,
+            height: self.height,
+            wrapping: self.wrapping,
+        }
+    }
+}
+
+fn resolve_axis(value: isize, length: usize, wrap: bool) -> Option<usize> {
+    if length == 0 {
+        None
+    } else if wrap {
+        Some(value.rem_euclid(length as isize) as usize)
+    } else if value >= 0 && (value as usize) < length {
+        Some(value as usize)
+    } else {
+        None
+    }
+}
+
+impl<T> Index<(usize, usize)> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, (row, col): (usize, usize)) -> &T {
+        self.get(row, col).unwrap_or_else(|| {
+            panic!(
+                "position ({}, {}) outside of {}x{} grid",
+                row, col, self.width, self.height
+            )
+        })
+    }
+}
+
+impl<T> IndexMut<(usize, usize)> for Grid<T> {
+    fn index_mut(&mut self, (row, col): (usize, usize)) -> &mut T {
+        let (width, height) = (self.width, self.height);
+
+        self.get_mut(row, col).unwrap_or_else(|| {
+            panic!(
+                "position ({}, {}) outside of {}x{} grid",
+                row, col, width, height
+            )
+        })
+    }
+}
+
+impl<T: fmt::Display> fmt::Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (i, row) in self.rows().enumerate() {
+            if i > 0 {
+                writeln!(f)?;
+            }
+            for cell in row {
+                write!(f, "{}", cell)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample() -> Grid<char> {
+        let lines = ["abc", "def"]
+            .iter()
+            .map(|l| l.to_string())
+            .collect::<Vec<String>>();
+
+        Grid::parse(&lines, |c| c).unwrap()
+    }
+
+    #[test]
+    fn it_parses_and_renders_a_grid() {
+        let grid = sample();
+        assert_eq!(grid.width(), 3);
+        assert_eq!(grid.height(), 2);
+        assert_eq!(grid[(1, 2)], 'f');
+        assert_eq!(grid.to_string(), "abc\ndef");
+
+        let ragged = vec!["abc".to_string(), "de".to_string()];
+        assert!(Grid::parse(&ragged, |c| c).is_err());
+    }
+
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    enum Light {
+        Off,
+        On,
+    }
+
+    impl TryFrom<char> for Light {
+        type Error = UnknownCell;
+
+        fn try_from(c: char) -> Result<Light, UnknownCell> {
+            match c {
+                '.' => Ok(Light::Off),
+                '#' => Ok(Light::On),
+                other => Err(UnknownCell(other)),
+            }
+        }
+    }
+
+    impl Cell for Light {
+        fn to_char(self) -> char {
+            match self {
+                Light::Off => '.',
+                Light::On => '#',
+            }
+        }
+    }
+
+    #[test]
+    fn it_parses_typed_cells() {
+        let lines = vec![".#".to_string(), "#.".to_string()];
+        let grid = Grid::<Light>::parse_cells(&lines).unwrap();
+        assert_eq!(grid[(0, 1)], Light::On);
+        assert_eq!(grid.map(|_, light| light.to_char()).to_string(), ".#\n#.");
+
+        let lines = vec![".#".to_string(), "#x".to_string()];
+        match Grid::<Light>::parse_cells(&lines) {
+            Err(InputError::Parse { line, reason, .. }) => {
+                assert_eq!(line, 2);
+                assert_eq!(reason, "unknown cell 'x' at column 2");
+            }
+            other => panic!("expected parse error, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn it_checks_bounds() {
+        let grid = sample();
+        assert_eq!(grid.get(0, 3), None);
+        assert_eq!(grid.get(2, 0), None);
+        assert_eq!(grid.get_signed(-1, 0), None);
+        assert_eq!(grid.get_signed(0, -1), None);
+    }
+
+    #[test]
+    fn it_wraps_around() {
+        let grid = sample().with_wrapping(Wrapping::Horizontal);
+        assert_eq!(grid.get_signed(0, 3), Some(&'a'));
+        assert_eq!(grid.get_signed(1, -1), Some(&'f'));
+        assert_eq!(grid.get_signed(2, 0), None);
+
+        let grid = grid.with_wrapping(Wrapping::Both);
+        assert_eq!(grid.get_signed(-1, 4), Some(&'e'));
+    }
+
+    #[test]
+    fn it_iterates_neighbours() {
+        let grid = sample();
+        let neighbours = grid.neighbours((0, 0)).map(|(_, c)| *c).collect::<String>();
+        assert_eq!(neighbours, "bde");
+
+        let neighbours = grid.neighbours((1, 1)).map(|(_, c)| *c).collect::<String>();
+        assert_eq!(neighbours, "abcdf");
+
+        let wrapped = sample().with_wrapping(Wrapping::Both);
+        assert_eq!(wrapped.neighbours((0, 0)).count(), 8);
+    }
+
+    #[test]
+    fn it_iterates_rows_and_columns() {
+        let grid = sample();
+        assert_eq!(
+            grid.rows().collect::<Vec<&[char]>>(),
+            vec![&['a', 'b', 'c'][..], &['d', 'e', 'f'][..]]
+        );
+        assert_eq!(grid.column(1).collect::<String>(), "be");
+        assert_eq!(
+            grid.columns()
+                .map(|column| column.collect::<String>())
+                .collect::<Vec<String>>(),
+            vec!["ad", "be", "cf"]
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "column 3 outside of 3x2 grid")]
+    fn it_rejects_columns_outside_of_the_grid() {
+        sample().column(3).count();
+    }
+
+    #[test]
+    #[should_panic(expected = "row 2 outside of 3x2 grid")]
+    fn it_rejects_rows_outside_of_the_grid() {
+        sample().row(2);
+    }
+
+    #[test]
+    fn it_maps_cells() {
+        let grid = sample().map(|(row, col), c| format!("{}{}{}", c, row, col));
+        assert_eq!(grid[(1, 0)], "d10");
+        assert_eq!(Grid::new(2, 2, 0).map(|_, n| n + 1).iter().count(), 4);
+    }
+}
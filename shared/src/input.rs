@@ -0,0 +1,78 @@
+use std::fmt;
+use std::io;
+use std::io::prelude::*;
+use std::path::{Path, PathBuf};
+
+use crate::InputError;
+
+/// Where to read a puzzle's input from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum InputSource {
+    Stdin,
+    Path(PathBuf),
+}
+
+impl InputSource {
+    /// `-` reads from stdin and any other argument is used as a path. Without an argument the
+    /// input is `input.txt` in `default_dir`, usually the day's crate directory, so that it is
+    /// found regardless of the current directory.
+    pub fn resolve(arg: Option<&str>, default_dir: &str) -> InputSource {
+        match arg {
+            Some("-") => InputSource::Stdin,
+            Some(path) => InputSource::Path(PathBuf::from(path)),
+            None => InputSource::Path(Path::new(default_dir).join("input.txt")),
+        }
+    }
+
+    pub fn read(&self) -> Result<Vec<String>, InputError> {
+        let contents = self.read_to_string()?;
+
+        Ok(contents.lines().map(|l| l.to_string()).collect())
+    }
+
+    /// The whole input, keeping its line endings.
+    pub fn read_to_string(&self) -> Result<String, InputError> {
+        match self {
+            InputSource::Path(path) => crate::try_read_file_to_string(&path.to_string_lossy()),
+            InputSource::Stdin => {
+                let mut contents = String::new();
+                io::stdin()
+                    .read_to_string(&mut contents)
+                    .map_err(|err| match err.kind() {
+                        io::ErrorKind::InvalidData => InputError::Encoding(self.to_string()),
+                        _ => InputError::Io(self.to_string(), err),
+                    })?;
+
+                Ok(contents)
+            }
+        }
+    }
+}
+
+impl fmt::Display for InputSource {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            InputSource::Stdin => write!(f, "<stdin>"),
+            InputSource::Path(path) => write!(f, "{}", path.display()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_resolves_input_sources() {
+        assert_eq!(InputSource::resolve(Some("-"), "day_1"), InputSource::Stdin);
+        assert_eq!(
+            InputSource::resolve(Some("other.txt"), "day_1"),
+            InputSource::Path(PathBuf::from("other.txt"))
+        );
+        assert_eq!(
+            InputSource::resolve(None, "/repo/day_1"),
+            InputSource::Path(PathBuf::from("/repo/day_1/input.txt"))
+        );
+        assert_eq!(InputSource::Stdin.to_string(), "<stdin>");
+    }
+}
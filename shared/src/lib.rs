@@ -1,33 +1,262 @@
+use std::error::Error;
+use std::fmt;
 use std::fs::File;
+use std::io;
 use std::io::prelude::*;
+use std::ops::RangeInclusive;
+use std::str::FromStr;
 
-pub fn read_file(filename: &str) -> Vec<String> {
-    let mut file = File::open(filename).unwrap();
+mod cli;
+mod grid;
+mod input;
+mod solver;
+
+pub use cli::{parse_flags, parse_input_and_flags, split_args, Args};
+pub use grid::{Cell, Grid, UnknownCell, Wrapping, NEIGHBOUR_OFFSETS};
+pub use input::InputSource;
+pub use solver::{
+    check_results, print_results, run, Answer, IntoAnswer, Part, PartResult, Puzzle, RunError,
+    Solver, Stage,
+};
+
+#[derive(Debug)]
+pub enum InputError {
+    Missing(String),
+    Io(String, io::Error),
+    Encoding(String),
+    Parse {
+        line: usize,
+        text: String,
+        reason: String,
+    },
+}
+
+impl InputError {
+    pub fn parse(line: usize, text: &str, reason: impl fmt::Display) -> InputError {
+        InputError::Parse {
+            line,
+            text: text.to_string(),
+            reason: reason.to_string(),
+        }
+    }
+}
+
+impl fmt::Display for InputError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            InputError::Missing(filename) => write!(f, "input file {} not found", filename),
+            InputError::Io(filename, err) => write!(f, "could not read {}: {}", filename, err),
+            InputError::Encoding(filename) => {
+                write!(f, "input file {} is not valid UTF-8", filename)
+            }
+            InputError::Parse { line, text, reason } => {
+                write!(f, "line {}: could not parse {:?}: {}", line, text, reason)
+            }
+        }
+    }
+}
+
+impl Error for InputError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            InputError::Io(_, err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+/// Reads a whole file, keeping its line endings.
+pub fn try_read_file_to_string(filename: &str) -> Result<String, InputError> {
+    let mut file = File::open(filename).map_err(|err| match err.kind() {
+        io::ErrorKind::NotFound => InputError::Missing(filename.to_string()),
+        _ => InputError::Io(filename.to_string(), err),
+    })?;
     let mut contents = String::new();
 
-    file.read_to_string(&mut contents).unwrap();
-    let lines = contents
+    file.read_to_string(&mut contents)
+        .map_err(|err| match err.kind() {
+            io::ErrorKind::InvalidData => InputError::Encoding(filename.to_string()),
+            _ => InputError::Io(filename.to_string(), err),
+        })?;
+
+    Ok(contents)
+}
+
+pub fn try_read_file(filename: &str) -> Result<Vec<String>, InputError> {
+    let lines = try_read_file_to_string(filename)?
         .lines()
         .map(|l| l.to_string())
         .collect::<Vec<String>>();
 
+    Ok(lines)
+}
+
+pub fn try_parse_lines<T>(filename: &str) -> Result<Vec<T>, InputError>
+where
+    T: FromStr,
+    T::Err: fmt::Display,
+{
+    parse_lines(&try_read_file(filename)?)
+}
+
+/// Parses every line into `T`, reporting the 1-based line number of the first failure.
+pub fn parse_lines<T>(lines: &[String]) -> Result<Vec<T>, InputError>
+where
+    T: FromStr,
+    T::Err: fmt::Display,
+{
     lines
+        .iter()
+        .enumerate()
+        .map(|(i, line)| {
+            line.trim()
+                .parse::<T>()
+                .map_err(|err| InputError::parse(i + 1, line, err))
+        })
+        .collect()
 }
 
-pub fn parse_input_to_character_matrix(input: &str) -> Vec<Vec<String>> {
-    input
-        .split("\n")
-        .map(|row| row.trim())
-        .filter(|row| row.len() > 0)
-        .map(|line| line.chars().map(|c| c.to_string()).collect())
-        .collect::<Vec<Vec<String>>>()
+/// Unwraps a parsed input, printing the error and exiting instead of panicking.
+pub fn unwrap_or_exit<T, E: fmt::Display>(result: Result<T, E>) -> T {
+    match result {
+        Ok(value) => value,
+        Err(err) => {
+            eprintln!("Error: {}", err);
+            std::process::exit(1);
+        }
+    }
+}
+
+pub fn read_file(filename: &str) -> Vec<String> {
+    match try_read_file(filename) {
+        Ok(lines) => lines,
+        Err(err) => panic!("{}", err),
+    }
+}
+
+/// Reads a file made of records: groups of lines separated by one or more blank lines.
+pub fn read_records(filename: &str) -> Result<Vec<Vec<String>>, InputError> {
+    Ok(parse_records(&try_read_file(filename)?))
+}
+
+/// A group of lines from a records file and where it came from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Record {
+    /// 1-based number of the record's first line in the input.
+    pub start: usize,
+    pub lines: Vec<String>,
+}
+
+impl Record {
+    /// 1-based numbers of the record's first and last line.
+    pub fn line_range(&self) -> RangeInclusive<usize> {
+        self.start..=self.start + self.lines.len() - 1
+    }
+}
+
+/// Groups lines into records separated by blank (or whitespace only) lines. Stray `\r` line
+/// endings are removed and no record is ever empty.
+pub fn parse_records(lines: &[String]) -> Vec<Vec<String>> {
+    parse_numbered_records(lines)
+        .into_iter()
+        .map(|record| record.lines)
+        .collect()
+}
+
+/// Like `parse_records`, but keeps each record's line numbers. Lines containing `\n` count
+/// as several lines.
+pub fn parse_numbered_records(lines: &[String]) -> Vec<Record> {
+    let mut records = vec![];
+    let mut current = Record {
+        start: 1,
+        lines: vec![],
+    };
+
+    for (i, line) in lines.iter().flat_map(|line| line.split('\n')).enumerate() {
+        let line = line.trim_end_matches('\r');
+
+        if line.trim().is_empty() {
+            if !current.lines.is_empty() {
+                records.push(current);
+            }
+            current = Record {
+                start: i + 2,
+                lines: vec![],
+            };
+        } else {
+            current.lines.push(line.to_string());
+        }
+    }
+
+    if !current.lines.is_empty() {
+        records.push(current);
+    }
+
+    records
 }
 
 pub fn parse_input_to_string_vec(input: &str) -> Vec<String> {
     input
         .split("\n")
         .map(|row| row.trim())
-        .filter(|row| row.len() > 0)
+        .filter(|row| !row.is_empty())
         .map(|r| r.to_string())
         .collect::<Vec<String>>()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_reports_missing_files() {
+        match try_read_file("does_not_exist.txt") {
+            Err(InputError::Missing(filename)) => assert_eq!(filename, "does_not_exist.txt"),
+            other => panic!("expected missing file error, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn it_reports_the_offending_line() {
+        let lines = vec!["1".to_string(), "2".to_string(), "x3".to_string()];
+        match parse_lines::<usize>(&lines) {
+            Err(InputError::Parse { line, text, .. }) => {
+                assert_eq!(line, 3);
+                assert_eq!(text, "x3");
+            }
+            other => panic!("expected parse error, got {:?}", other),
+        }
+
+        assert_eq!(parse_lines::<usize>(&lines[0..2]).unwrap(), vec![1, 2]);
+    }
+
+    #[test]
+    fn it_groups_records_separated_by_blank_lines() {
+        let lines = ["", "a\r", "b", "\r", "  ", "c", "", ""]
+            .iter()
+            .map(|l| l.to_string())
+            .collect::<Vec<String>>();
+
+        assert_eq!(parse_records(&lines), vec![vec!["a", "b"], vec!["c"]]);
+        assert_eq!(parse_records(&[]), Vec::<Vec<String>>::new());
+        assert_eq!(
+            parse_records(&["x\r\n\r\ny".to_string()]),
+            vec![vec!["x"], vec!["y"]]
+        );
+    }
+
+    #[test]
+    fn it_numbers_record_lines() {
+        let lines = ["", "a", "b", "", "", "c", "d\ne"]
+            .iter()
+            .map(|l| l.to_string())
+            .collect::<Vec<String>>();
+        let records = parse_numbered_records(&lines);
+
+        assert_eq!(
+            records.iter().map(|r| r.line_range()).collect::<Vec<_>>(),
+            vec![2..=3, 6..=8]
+        );
+        assert_eq!(records[1].lines, vec!["c", "d", "e"]);
+    }
+}
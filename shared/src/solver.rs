@@ -0,0 +1,276 @@
+use std::error::Error;
+use std::fmt;
+use std::hint;
+use std::time::{Duration, Instant};
+
+use crate::{InputError, InputSource};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Part {
+    One,
+    Two,
+}
+
+impl Part {
+    pub const ALL: [Part; 2] = [Part::One, Part::Two];
+
+    pub fn number(&self) -> u8 {
+        match self {
+            Part::One => 1,
+            Part::Two => 2,
+        }
+    }
+
+    pub fn from_number(number: u8) -> Option<Part> {
+        match number {
+            1 => Some(Part::One),
+            2 => Some(Part::Two),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for Part {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.number())
+    }
+}
+
+/// A timed step of solving a puzzle.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Stage {
+    Parse,
+    Part(Part),
+}
+
+impl Stage {
+    pub const ALL: [Stage; 3] = [Stage::Parse, Stage::Part(Part::One), Stage::Part(Part::Two)];
+}
+
+impl fmt::Display for Stage {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Stage::Parse => write!(f, "parse"),
+            Stage::Part(part) => write!(f, "part {}", part),
+        }
+    }
+}
+
+/// A puzzle answer kept as a value so that it can be rendered as text or serialized as-is.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Answer {
+    Integer(i128),
+    Text(String),
+}
+
+macro_rules! integer_answer {
+    ($($t:ty),*) => {
+        $(
+            impl From<$t> for Answer {
+                fn from(answer: $t) -> Answer {
+                    Answer::Integer(answer as i128)
+                }
+            }
+        )*
+    };
+}
+
+integer_answer!(i32, i64, isize, u32, u64, usize);
+
+impl From<String> for Answer {
+    fn from(answer: String) -> Answer {
+        Answer::Text(answer)
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(answer: &str) -> Answer {
+        Answer::Text(answer.to_string())
+    }
+}
+
+/// What a part returns. Parts that can fail return a `Result`, whose error is reported in place
+/// of the answer so that it can not be mistaken for a missing one.
+pub trait IntoAnswer {
+    fn into_answer(self) -> Result<Answer, String>;
+}
+
+impl<T: Into<Answer>> IntoAnswer for T {
+    fn into_answer(self) -> Result<Answer, String> {
+        Ok(self.into())
+    }
+}
+
+impl<T: Into<Answer>, E: fmt::Display> IntoAnswer for Result<T, E> {
+    fn into_answer(self) -> Result<Answer, String> {
+        self.map(Into::into).map_err(|err| err.to_string())
+    }
+}
+
+impl fmt::Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Answer::Integer(answer) => write!(f, "{}", answer),
+            Answer::Text(answer) => write!(f, "{}", answer),
+        }
+    }
+}
+
+/// A single day's puzzle. `parse` runs once and both parts share the parsed input.
+///
+/// Parts return `None` when the input has no answer (or the part is not solved yet), and an
+/// `Answer` that is a `Result` when solving can fail.
+pub trait Solver {
+    type Input;
+    type Answer: IntoAnswer;
+
+    fn day(&self) -> u8;
+    /// Directory holding the day's `input.txt`, normally `env!("CARGO_MANIFEST_DIR")`.
+    fn input_dir(&self) -> &'static str;
+    fn parse(&self, lines: &[String]) -> Result<Self::Input, InputError>;
+    fn part_one(&self, input: &Self::Input) -> Option<Self::Answer>;
+    fn part_two(&self, input: &Self::Input) -> Option<Self::Answer>;
+}
+
+#[derive(Debug)]
+pub struct PartResult {
+    pub part: Part,
+    /// The answer, `None` when there is none, or why the part failed.
+    pub answer: Result<Option<Answer>, String>,
+    pub elapsed: Duration,
+}
+
+/// Why `run` could not answer every part.
+#[derive(Debug)]
+pub enum RunError {
+    Input(InputError),
+    Part(Part, String),
+}
+
+impl fmt::Display for RunError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            RunError::Input(err) => write!(f, "{}", err),
+            RunError::Part(part, reason) => write!(f, "part {} failed: {}", part, reason),
+        }
+    }
+}
+
+impl Error for RunError {}
+
+impl From<InputError> for RunError {
+    fn from(err: InputError) -> RunError {
+        RunError::Input(err)
+    }
+}
+
+/// Turns the first failed part into an error.
+pub fn check_results(results: &[PartResult]) -> Result<(), RunError> {
+    match results
+        .iter()
+        .find_map(|r| r.answer.as_ref().err().map(|e| (r.part, e)))
+    {
+        Some((part, reason)) => Err(RunError::Part(part, reason.to_string())),
+        None => Ok(()),
+    }
+}
+
+/// Object safe view of a `Solver` so that days with different input and answer types can
+/// live in the same registry.
+pub trait Puzzle {
+    fn day(&self) -> u8;
+    fn input_dir(&self) -> &'static str;
+    fn solve(&self, lines: &[String], parts: &[Part]) -> Result<Vec<PartResult>, InputError>;
+
+    /// Times `iterations` runs of every stage. The parts reuse a single parsed input so that
+    /// their samples exclude parsing.
+    fn bench(
+        &self,
+        lines: &[String],
+        iterations: usize,
+    ) -> Result<Vec<(Stage, Vec<Duration>)>, InputError>;
+}
+
+impl<S: Solver> Puzzle for S {
+    fn day(&self) -> u8 {
+        Solver::day(self)
+    }
+
+    fn input_dir(&self) -> &'static str {
+        Solver::input_dir(self)
+    }
+
+    fn solve(&self, lines: &[String], parts: &[Part]) -> Result<Vec<PartResult>, InputError> {
+        let input = self.parse(lines)?;
+
+        Ok(parts
+            .iter()
+            .map(|part| {
+                let start = Instant::now();
+                let answer = match part {
+                    Part::One => self.part_one(&input),
+                    Part::Two => self.part_two(&input),
+                };
+
+                PartResult {
+                    part: *part,
+                    answer: answer.map(IntoAnswer::into_answer).transpose(),
+                    elapsed: start.elapsed(),
+                }
+            })
+            .collect())
+    }
+
+    fn bench(
+        &self,
+        lines: &[String],
+        iterations: usize,
+    ) -> Result<Vec<(Stage, Vec<Duration>)>, InputError> {
+        let mut parse_samples = vec![];
+        for _ in 0..iterations {
+            let start = Instant::now();
+            hint::black_box(self.parse(lines)?);
+            parse_samples.push(start.elapsed());
+        }
+
+        let input = self.parse(lines)?;
+        let mut samples = vec![(Stage::Parse, parse_samples)];
+        for part in Part::ALL.iter() {
+            let part_samples = (0..iterations)
+                .map(|_| {
+                    let start = Instant::now();
+                    hint::black_box(match part {
+                        Part::One => self.part_one(&input).map(IntoAnswer::into_answer),
+                        Part::Two => self.part_two(&input).map(IntoAnswer::into_answer),
+                    });
+                    start.elapsed()
+                })
+                .collect();
+
+            samples.push((Stage::Part(*part), part_samples));
+        }
+
+        Ok(samples)
+    }
+}
+
+/// Solves and prints the given parts. Failed parts are printed too, then reported as an error.
+pub fn run(puzzle: &dyn Puzzle, input: &InputSource, parts: &[Part]) -> Result<(), RunError> {
+    let lines = input.read()?;
+    let answers = puzzle.solve(&lines, parts)?;
+
+    print_results(puzzle.day(), &answers);
+
+    check_results(&answers)
+}
+
+pub fn print_results(day: u8, results: &[PartResult]) {
+    println!("Day {}", day);
+    for result in results {
+        match &result.answer {
+            Ok(Some(answer)) => println!("  Part {}: {}", result.part, answer),
+            Ok(None) => println!("  Part {}: no answer", result.part),
+            Err(reason) => println!("  Part {}: error: {}", result.part, reason),
+        }
+    }
+}